@@ -0,0 +1,85 @@
+//! Compares the dijkstra priority queues on a large day 16 style maze.
+//!
+//! `cargo run --release -p common --example queue_bench [cells] [seed]`
+
+use std::time::{Duration, Instant};
+
+use common::{
+    BinaryHeapQueue, BucketQueue, Dijkstra, Graph, IdTrait, PlainNode, PriorityQueue, RadixHeap,
+    braided_maze,
+};
+
+// (col, row, direction) with directions clockwise starting east
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct State(usize, usize, u8);
+
+impl IdTrait for State {}
+
+const STEP: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+fn reindeer_graph(maze: &str) -> (Graph<PlainNode<State>>, State) {
+    let map: Vec<Vec<u8>> = maze.lines().map(|l| l.bytes().collect()).collect();
+    let mut nodes = vec![];
+    let mut edges = vec![];
+    let mut start = None;
+
+    for (row, line) in map.iter().enumerate() {
+        for (col, &c) in line.iter().enumerate() {
+            if c == b'#' {
+                continue;
+            }
+            if c == b'S' {
+                start = Some(State(col, row, 0));
+            }
+
+            for dir in 0..4 {
+                let here = State(col, row, dir);
+                nodes.push(here);
+                edges.push((here, State(col, row, (dir + 1) % 4), 1000));
+                edges.push((here, State(col, row, (dir + 3) % 4), 1000));
+
+                let (dx, dy) = STEP[dir as usize];
+                let (x, y) = (col.wrapping_add_signed(dx), row.wrapping_add_signed(dy));
+                if map
+                    .get(y)
+                    .and_then(|l| l.get(x))
+                    .is_some_and(|&c| c != b'#')
+                {
+                    edges.push((here, State(x, y, dir), 1));
+                }
+            }
+        }
+    }
+
+    (Graph::from_edges(nodes, edges), start.unwrap())
+}
+
+fn time<Q>(graph: &Graph<PlainNode<State>>, start: State, queue: Q) -> (Duration, Dijkstra<State>)
+where
+    Q: PriorityQueue<State>,
+{
+    let now = Instant::now();
+    let result = graph.dijkstra_using(&start, None, queue);
+    (now.elapsed(), result)
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1).map(|a| a.parse().unwrap());
+    let cells = args.next().unwrap_or(300);
+    let seed = args.next().unwrap_or(1) as u64;
+
+    let maze = braided_maze(cells, cells, 0.3, seed);
+    let (graph, start) = reindeer_graph(&maze);
+
+    let (heap_time, expected) = time(&graph, start, BinaryHeapQueue::default());
+    println!("{cells}x{cells} cell maze, {} states", expected.dist.len());
+    println!("binary heap  {heap_time:>10.2?}");
+
+    for (name, (elapsed, result)) in [
+        ("radix heap", time(&graph, start, RadixHeap::default())),
+        ("bucket queue", time(&graph, start, BucketQueue::new(1000))),
+    ] {
+        assert_eq!(result.dist, expected.dist, "{name} disagrees");
+        println!("{name:<12} {elapsed:>10.2?}");
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crate::Grid;
+
+// moves the cursor home and clears the screen before every frame
+const CLEAR: &str = "\x1b[H\x1b[2J";
+
+/// Collects rendered frames of a simulation (e.g. from a [`crate::Renderer`]) and writes them as
+/// an asciinema v2 `.cast` file, which `asciinema play` replays in the terminal.
+///
+/// Frames are spaced `frame_delay` apart, so the recording doesn't depend on how long the
+/// simulation took.
+#[derive(Debug, Clone)]
+pub struct CastRecorder {
+    width: usize,
+    height: usize,
+    frame_delay: Duration,
+    title: Option<String>,
+    frames: Vec<String>,
+}
+
+impl CastRecorder {
+    /// A recording for a terminal of `width` x `height` characters.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            frame_delay: Duration::from_millis(100),
+            title: None,
+            frames: vec![],
+        }
+    }
+
+    /// A recording just big enough for frames of `grid`.
+    pub fn for_grid<T>(grid: &Grid<T>) -> Self {
+        Self::new(grid.width(), grid.height() + 1)
+    }
+
+    /// Time between two frames, 100ms unless set.
+    pub fn frame_delay(self, frame_delay: Duration) -> Self {
+        Self {
+            frame_delay,
+            ..self
+        }
+    }
+
+    pub fn title(self, title: &str) -> Self {
+        Self {
+            title: Some(title.to_string()),
+            ..self
+        }
+    }
+
+    /// Adds a frame, the full screen contents after one step.
+    pub fn record(&mut self, frame: &str) {
+        self.frames.push(frame.to_string());
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Writes the header line followed by one output event per frame.
+    pub fn write_to<W>(&self, out: &mut W) -> io::Result<()>
+    where
+        W: Write + ?Sized,
+    {
+        write!(
+            out,
+            "{{\"version\": 2, \"width\": {}, \"height\": {}",
+            self.width, self.height
+        )?;
+        if let Some(title) = &self.title {
+            write!(out, ", \"title\": {}", json_string(title))?;
+        }
+        writeln!(out, "}}")?;
+
+        for (i, frame) in self.frames.iter().enumerate() {
+            let time = self.frame_delay.as_secs_f64() * i as f64;
+            // the terminal is in raw mode, so lines need an explicit carriage return
+            let data = format!("{CLEAR}{}", frame.replace('\n', "\r\n"));
+            writeln!(out, "[{time:.6}, \"o\", {}]", json_string(&data))?;
+        }
+        Ok(())
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Renderer;
+
+    #[test]
+    fn test_cast_file() {
+        let mut grid: Grid<char> = "@.\n..".parse().unwrap();
+        let mut recorder = CastRecorder::for_grid(&grid)
+            .frame_delay(Duration::from_millis(250))
+            .title("robot \"demo\"");
+
+        for step in [(1, 0), (1, 1)] {
+            recorder.record(&Renderer::new(&grid, |&c| c).render());
+            grid = grid.map(|_| '.');
+            grid[step] = '@';
+        }
+        recorder.record(&Renderer::new(&grid, |&c| c).render());
+        assert_eq!(recorder.len(), 3);
+
+        let mut out = vec![];
+        recorder.write_to(&mut out).unwrap();
+        let cast = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = cast.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            r#"{"version": 2, "width": 2, "height": 3, "title": "robot \"demo\""}"#
+        );
+        assert_eq!(
+            lines[1],
+            r#"[0.000000, "o", "\u001b[H\u001b[2J@.\r\n..\r\n"]"#
+        );
+        assert_eq!(
+            lines[3],
+            r#"[0.500000, "o", "\u001b[H\u001b[2J..\r\n.@\r\n"]"#
+        );
+        assert_eq!(lines.len(), 4);
+    }
+}
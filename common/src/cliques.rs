@@ -0,0 +1,274 @@
+use crate::{IdTrait, UndirectedView};
+
+// fixed size set of node indices, one bit per node
+#[derive(Debug, Clone)]
+struct NodeSet(Vec<u64>);
+
+impl NodeSet {
+    fn empty(n: usize) -> Self {
+        Self(vec![0; n.div_ceil(64)])
+    }
+
+    fn full(n: usize) -> Self {
+        let mut set = Self::empty(n);
+        for i in 0..n {
+            set.insert(i);
+        }
+        set
+    }
+
+    fn insert(&mut self, i: usize) {
+        self.0[i / 64] |= 1 << (i % 64);
+    }
+
+    fn remove(&mut self, i: usize) {
+        self.0[i / 64] &= !(1 << (i % 64));
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.iter().all(|&w| w == 0)
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        Self(self.0.iter().zip(&other.0).map(|(a, b)| a & b).collect())
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        Self(self.0.iter().zip(&other.0).map(|(a, b)| a & !b).collect())
+    }
+
+    fn intersection_len(&self, other: &Self) -> u32 {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .map(|(a, b)| (a & b).count_ones())
+            .sum()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.iter().enumerate().flat_map(|(w, &word)| {
+            (0..64)
+                .filter(move |b| word & (1 << b) != 0)
+                .map(move |b| w * 64 + b)
+        })
+    }
+}
+
+impl<N> UndirectedView<N>
+where
+    N: IdTrait,
+{
+    /// Every maximal clique (a fully connected group that can't be extended by another node),
+    /// found with Bron-Kerbosch with pivoting. Each clique is sorted, and so is the list. Empty for
+    /// an empty view.
+    pub fn maximal_cliques(&self) -> Vec<Vec<N>> {
+        let neighbors = self.neighbor_sets();
+        let n = neighbors.len();
+        if n == 0 {
+            return vec![];
+        }
+
+        let mut cliques = vec![];
+        bron_kerbosch(
+            &neighbors,
+            &mut vec![],
+            NodeSet::full(n),
+            NodeSet::empty(n),
+            &mut cliques,
+        );
+
+        let mut cliques: Vec<Vec<N>> = cliques
+            .into_iter()
+            .map(|mut clique| {
+                clique.sort();
+                clique.into_iter().map(|i| self.id(i).clone()).collect()
+            })
+            .collect();
+        cliques.sort();
+        cliques
+    }
+
+    /// The largest clique, preferring the smallest one in sorted order if there are several.
+    /// Empty for an empty view.
+    pub fn maximum_clique(&self) -> Vec<N> {
+        self.maximal_cliques()
+            .into_iter()
+            .rev()
+            .max_by_key(|clique| clique.len())
+            .unwrap_or_default()
+    }
+
+    /// Counts the cliques of exactly `k` nodes that contain at least one node matching
+    /// `predicate`, e.g. the triangles (`k = 3`) containing a node whose name starts with `t`.
+    pub fn count_k_cliques<F>(&self, k: usize, predicate: F) -> usize
+    where
+        F: Fn(&N) -> bool,
+    {
+        if k == 0 {
+            return 0;
+        }
+
+        let neighbors = self.neighbor_sets();
+        let matches: Vec<_> = self.nodes().iter().map(predicate).collect();
+
+        // extend cliques in increasing index order so every clique is found exactly once
+        fn count(
+            neighbors: &[NodeSet],
+            matches: &[bool],
+            candidates: &NodeSet,
+            remaining: usize,
+            matched: bool,
+        ) -> usize {
+            if remaining == 0 {
+                return matched as usize;
+            }
+
+            candidates
+                .iter()
+                .map(|v| {
+                    let mut next = candidates.intersection(&neighbors[v]);
+                    for u in 0..=v {
+                        next.remove(u);
+                    }
+                    count(
+                        neighbors,
+                        matches,
+                        &next,
+                        remaining - 1,
+                        matched || matches[v],
+                    )
+                })
+                .sum()
+        }
+
+        count(
+            &neighbors,
+            &matches,
+            &NodeSet::full(neighbors.len()),
+            k,
+            false,
+        )
+    }
+
+    fn neighbor_sets(&self) -> Vec<NodeSet> {
+        let n = self.adj().len();
+        self.adj()
+            .iter()
+            .map(|adj| {
+                let mut set = NodeSet::empty(n);
+                for &j in adj {
+                    set.insert(j);
+                }
+                set
+            })
+            .collect()
+    }
+}
+
+fn bron_kerbosch(
+    neighbors: &[NodeSet],
+    clique: &mut Vec<usize>,
+    mut candidates: NodeSet,
+    mut excluded: NodeSet,
+    cliques: &mut Vec<Vec<usize>>,
+) {
+    if candidates.is_empty() {
+        if excluded.is_empty() {
+            cliques.push(clique.clone());
+        }
+        return;
+    }
+
+    // the pivot with the most candidate neighbours leaves the fewest branches to explore
+    let pivot = candidates
+        .iter()
+        .chain(excluded.iter())
+        .max_by_key(|&u| candidates.intersection_len(&neighbors[u]))
+        .unwrap();
+
+    for v in candidates
+        .difference(&neighbors[pivot])
+        .iter()
+        .collect::<Vec<_>>()
+    {
+        clique.push(v);
+        bron_kerbosch(
+            neighbors,
+            clique,
+            candidates.intersection(&neighbors[v]),
+            excluded.intersection(&neighbors[v]),
+            cliques,
+        );
+        clique.pop();
+
+        candidates.remove(v);
+        excluded.insert(v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_graphs::*;
+
+    const LAN_PARTY: &str = "
+        kh-tc qp-kh de-cg ka-co yn-aq qp-ub cg-tb vc-aq
+        tb-ka wh-tc yn-cg kh-ub ta-co de-co tc-td tb-wq
+        wh-td ta-ka td-qp aq-cg wq-ub ub-vc de-ta wq-aq
+        wq-vc wh-yn ka-de kh-ta co-tc wh-qp tb-vc td-yn
+    ";
+
+    // the example network from 2024 day 23, computers are numbered in sorted order of their names
+    fn lan_party() -> (Vec<&'static str>, Vec<(usize, usize, usize)>) {
+        let links: Vec<_> = LAN_PARTY
+            .split_whitespace()
+            .map(|link| link.split_once('-').unwrap())
+            .collect();
+        let mut names: Vec<_> = links.iter().flat_map(|&(a, b)| [a, b]).collect();
+        names.sort();
+        names.dedup();
+
+        let id = |name| names.binary_search(&name).unwrap();
+        let edges = links.iter().map(|&(a, b)| (id(a), id(b), 1)).collect();
+        (names, edges)
+    }
+
+    #[test]
+    fn test_maximal_cliques_small() {
+        // a triangle 0-1-2 sharing node 2 with the edge 2-3, and an isolated edge 4-5
+        let graph = create_test_graph(vec![(0, 1, 1), (1, 2, 1), (2, 0, 1), (2, 3, 1), (4, 5, 1)]);
+        let view = graph.undirected();
+
+        assert_eq!(
+            view.maximal_cliques(),
+            vec![ids(&[0, 1, 2]), ids(&[2, 3]), ids(&[4, 5])]
+        );
+        assert_eq!(view.maximum_clique(), ids(&[0, 1, 2]));
+    }
+
+    #[test]
+    fn test_maximum_clique_ties() {
+        let graph = create_test_graph(vec![(3, 4, 1), (0, 1, 1)]);
+        assert_eq!(graph.undirected().maximum_clique(), ids(&[0, 1]));
+
+        let graph = create_test_graph(vec![]);
+        assert!(graph.undirected().maximum_clique().is_empty());
+        assert!(graph.undirected().maximal_cliques().is_empty());
+    }
+
+    #[test]
+    fn test_lan_party() {
+        let (names, edges) = lan_party();
+        let graph = create_test_graph(edges);
+        let view = graph.undirected();
+
+        let password: Vec<_> = view.maximum_clique().iter().map(|n| names[n.0]).collect();
+        assert_eq!(password.join(","), "co,de,ka,ta");
+
+        // 12 triangles overall, 7 of them with a computer whose name starts with a 't'
+        assert_eq!(view.count_k_cliques(3, |_| true), 12);
+        assert_eq!(view.count_k_cliques(3, |n| names[n.0].starts_with('t')), 7);
+        assert_eq!(view.count_k_cliques(4, |_| true), 1);
+        assert_eq!(view.count_k_cliques(1, |n| n.0 == 0), 1);
+        assert_eq!(view.count_k_cliques(0, |_| true), 0);
+    }
+}
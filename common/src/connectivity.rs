@@ -0,0 +1,352 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{Graph, IdTrait, Node, UndirectedView};
+
+impl<N> UndirectedView<N>
+where
+    N: IdTrait,
+{
+    /// Edges whose removal disconnects their component, as `(smaller, larger)` pairs in ascending
+    /// order.
+    pub fn bridges(&self) -> Vec<(N, N)> {
+        let (bridges, _) = self.lowlink();
+        let mut bridges: Vec<_> = bridges
+            .into_iter()
+            .map(|(a, b)| {
+                let (a, b) = (self.id(a).clone(), self.id(b).clone());
+                if a < b { (a, b) } else { (b, a) }
+            })
+            .collect();
+        bridges.sort();
+        bridges
+    }
+
+    /// Nodes whose removal disconnects their component, in ascending order.
+    pub fn articulation_points(&self) -> Vec<N> {
+        let (_, is_cut) = self.lowlink();
+        is_cut
+            .into_iter()
+            .enumerate()
+            .filter(|(_, cut)| *cut)
+            .map(|(i, _)| self.id(i).clone())
+            .collect()
+    }
+
+    // Tarjan's lowlink DFS, done iteratively so large mazes don't blow the stack.
+    // Returns the bridges (as index pairs) and a per-node articulation point flag.
+    fn lowlink(&self) -> (Vec<(usize, usize)>, Vec<bool>) {
+        let adj = self.adj();
+        let mut disc = vec![usize::MAX; adj.len()];
+        let mut low = vec![0; adj.len()];
+        let mut is_cut = vec![false; adj.len()];
+        let mut bridges = vec![];
+        let mut timer = 0;
+
+        for root in 0..adj.len() {
+            if disc[root] != usize::MAX {
+                continue;
+            }
+
+            disc[root] = timer;
+            low[root] = timer;
+            timer += 1;
+
+            let mut root_children = 0;
+            // (node, parent, index of the next neighbour to look at)
+            let mut stack = vec![(root, usize::MAX, 0)];
+
+            while let Some(&(u, parent, next)) = stack.last() {
+                if next < adj[u].len() {
+                    stack.last_mut().unwrap().2 += 1;
+
+                    let v = adj[u][next];
+                    if v == parent {
+                        continue;
+                    }
+
+                    if disc[v] == usize::MAX {
+                        disc[v] = timer;
+                        low[v] = timer;
+                        timer += 1;
+                        if u == root {
+                            root_children += 1;
+                        }
+                        stack.push((v, u, 0));
+                    } else {
+                        low[u] = low[u].min(disc[v]);
+                    }
+                } else {
+                    stack.pop();
+                    if parent == usize::MAX {
+                        continue;
+                    }
+
+                    low[parent] = low[parent].min(low[u]);
+                    if low[u] > disc[parent] {
+                        bridges.push((parent, u));
+                    }
+                    if parent != root && low[u] >= disc[parent] {
+                        is_cut[parent] = true;
+                    }
+                }
+            }
+
+            if root_children > 1 {
+                is_cut[root] = true;
+            }
+        }
+
+        (bridges, is_cut)
+    }
+}
+
+/// Dominator tree of a directed graph, rooted at the node the search started from.
+///
+/// A node `d` dominates `n` if every path from the root to `n` goes through `d`. Only nodes
+/// reachable from the root are part of the tree.
+#[derive(Debug, Clone)]
+pub struct Dominators<N>
+where
+    N: IdTrait,
+{
+    root: N,
+    idom: HashMap<N, N>,
+}
+
+impl<N> Dominators<N>
+where
+    N: IdTrait,
+{
+    pub fn root(&self) -> &N {
+        &self.root
+    }
+
+    /// The closest strict dominator of `node`, `None` for the root and unreachable nodes.
+    pub fn immediate_dominator(&self, node: &N) -> Option<&N> {
+        self.idom.get(node)
+    }
+
+    /// All dominators of `node`, starting with `node` itself and ending with the root.
+    ///
+    /// Returns `None` if `node` is not reachable from the root.
+    pub fn dominators(&self, node: &N) -> Option<Vec<N>> {
+        if *node != self.root && !self.idom.contains_key(node) {
+            return None;
+        }
+
+        let mut chain = vec![node.clone()];
+        let mut current = node;
+        while let Some(idom) = self.idom.get(current) {
+            chain.push(idom.clone());
+            current = idom;
+        }
+        Some(chain)
+    }
+
+    pub fn dominates(&self, dominator: &N, node: &N) -> bool {
+        self.dominators(node)
+            .is_some_and(|chain| chain.contains(dominator))
+    }
+
+    /// Children of every node in the dominator tree, sorted.
+    pub fn tree(&self) -> HashMap<N, Vec<N>> {
+        let mut tree: HashMap<N, Vec<N>> = HashMap::new();
+        for (node, idom) in &self.idom {
+            tree.entry(idom.clone()).or_default().push(node.clone());
+        }
+        for children in tree.values_mut() {
+            children.sort();
+        }
+        tree
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Node,
+{
+    /// Computes the dominator tree of the nodes reachable from `start`, using the iterative
+    /// algorithm by Cooper, Harvey and Kennedy.
+    pub fn dominators(&self, start: &T::NodeIdType) -> Dominators<T::NodeIdType> {
+        let successors = |n: &T::NodeIdType| {
+            self.adjacency_list
+                .get(n)
+                .into_iter()
+                .flatten()
+                .map(|e| e.node.clone())
+        };
+
+        // postorder of an (iterative) dfs from start
+        let mut postorder = vec![];
+        let mut visited = HashSet::from([start.clone()]);
+        let mut stack = vec![(start.clone(), successors(start))];
+        while let Some((node, children)) = stack.last_mut() {
+            if let Some(child) = children.next() {
+                if visited.insert(child.clone()) {
+                    let grandchildren = successors(&child);
+                    stack.push((child, grandchildren));
+                }
+            } else {
+                postorder.push(node.clone());
+                stack.pop();
+            }
+        }
+
+        let order: HashMap<_, _> = postorder
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.clone(), i))
+            .collect();
+        let mut preds: Vec<Vec<usize>> = vec![vec![]; postorder.len()];
+        for (i, node) in postorder.iter().enumerate() {
+            for succ in successors(node) {
+                preds[order[&succ]].push(i);
+            }
+        }
+
+        // indices are postorder numbers, the root is always the last one
+        let root = postorder.len() - 1;
+        let mut idom = vec![usize::MAX; postorder.len()];
+        idom[root] = root;
+
+        let intersect = |idom: &[usize], mut a: usize, mut b: usize| {
+            while a != b {
+                while a < b {
+                    a = idom[a];
+                }
+                while b < a {
+                    b = idom[b];
+                }
+            }
+            a
+        };
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for b in (0..root).rev() {
+                let mut new_idom = usize::MAX;
+                for &p in preds[b].iter().filter(|&&p| idom[p] != usize::MAX) {
+                    new_idom = if new_idom == usize::MAX {
+                        p
+                    } else {
+                        intersect(&idom, new_idom, p)
+                    };
+                }
+                if idom[b] != new_idom {
+                    idom[b] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+
+        Dominators {
+            root: start.clone(),
+            idom: (0..root)
+                .map(|i| (postorder[i].clone(), postorder[idom[i]].clone()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_graphs::*;
+
+    #[test]
+    fn test_bridges_and_articulation_points() {
+        // two triangles joined by the edge 2-3, with a tail 5-6
+        let graph = create_test_graph(vec![
+            (0, 1, 1),
+            (1, 2, 1),
+            (2, 0, 1),
+            (2, 3, 1),
+            (3, 4, 1),
+            (4, 5, 1),
+            (5, 3, 1),
+            (5, 6, 1),
+        ]);
+        let view = graph.undirected();
+
+        assert_eq!(
+            view.bridges(),
+            vec![
+                (TestNodeId(2), TestNodeId(3)),
+                (TestNodeId(5), TestNodeId(6))
+            ]
+        );
+        assert_eq!(view.articulation_points(), ids(&[2, 3, 5]));
+    }
+
+    #[test]
+    fn test_cycle_has_no_bridges() {
+        let graph = create_test_graph(vec![(0, 1, 1), (1, 2, 2), (2, 0, 3)]);
+        let view = graph.undirected();
+
+        assert!(view.bridges().is_empty());
+        assert!(view.articulation_points().is_empty());
+    }
+
+    #[test]
+    fn test_edges_in_both_directions_are_still_bridges() {
+        let graph = create_test_graph(vec![(0, 1, 1), (1, 0, 1), (1, 2, 1), (2, 1, 1)]);
+        let view = graph.undirected();
+
+        assert_eq!(
+            view.bridges(),
+            vec![
+                (TestNodeId(0), TestNodeId(1)),
+                (TestNodeId(1), TestNodeId(2))
+            ]
+        );
+        assert_eq!(view.articulation_points(), ids(&[1]));
+    }
+
+    #[test]
+    fn test_dominators_diamond() {
+        let graph = create_test_graph(vec![(0, 1, 1), (0, 2, 1), (1, 3, 1), (2, 3, 1), (3, 4, 1)]);
+        let dominators = graph.dominators(&TestNodeId(0));
+
+        assert_eq!(dominators.immediate_dominator(&TestNodeId(0)), None);
+        assert_eq!(
+            dominators.immediate_dominator(&TestNodeId(3)),
+            Some(&TestNodeId(0))
+        );
+        assert_eq!(dominators.dominators(&TestNodeId(4)), Some(ids(&[4, 3, 0])));
+        assert!(dominators.dominates(&TestNodeId(3), &TestNodeId(4)));
+        assert!(!dominators.dominates(&TestNodeId(1), &TestNodeId(3)));
+    }
+
+    #[test]
+    fn test_dominators_complex_graph() {
+        // same graph as test_complex_path_selection
+        let graph = create_test_graph(vec![
+            (0, 1, 1),
+            (1, 3, 2),
+            (3, 4, 1),
+            (0, 2, 3),
+            (2, 4, 2),
+            (0, 5, 2),
+            (5, 4, 1),
+            (2, 5, 1),
+            (3, 4, 1),
+            (2, 6, 2),
+            (4, 7, 5),
+        ]);
+        let dominators = graph.dominators(&TestNodeId(0));
+
+        // every path to 7 must go through 4, and every path to 6 through 2
+        assert_eq!(dominators.dominators(&TestNodeId(7)), Some(ids(&[7, 4, 0])));
+        assert_eq!(dominators.dominators(&TestNodeId(6)), Some(ids(&[6, 2, 0])));
+        assert_eq!(dominators.tree()[&TestNodeId(0)], ids(&[1, 2, 4, 5]));
+    }
+
+    #[test]
+    fn test_dominators_unreachable() {
+        let graph = create_test_graph(vec![(0, 1, 1), (2, 1, 1)]);
+        let dominators = graph.dominators(&TestNodeId(0));
+
+        assert_eq!(dominators.dominators(&TestNodeId(2)), None);
+        assert_eq!(dominators.dominators(&TestNodeId(1)), Some(ids(&[1, 0])));
+    }
+}
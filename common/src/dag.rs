@@ -0,0 +1,490 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::{Graph, Graphable, IdTrait, Node};
+
+/// Returned by DAG algorithms when the graph turns out to contain a cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<N> {
+    /// a node that lies on (or is only reachable through) a cycle
+    pub node: N,
+}
+
+/// A path through a graph together with its total cost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightedPath<N> {
+    pub nodes: Vec<N>,
+    pub cost: usize,
+}
+
+/// Transitive closure of a DAG, stored as one bitset row per node so that reachability queries
+/// are a single lookup.
+#[derive(Debug, Clone)]
+pub struct Reachability<N>
+where
+    N: IdTrait,
+{
+    ids: Vec<N>,
+    index: HashMap<N, usize>,
+    words_per_row: usize,
+    bits: Vec<u64>,
+}
+
+impl<N> Reachability<N>
+where
+    N: IdTrait,
+{
+    /// All nodes of the graph, in topological order.
+    pub fn nodes(&self) -> &[N] {
+        &self.ids
+    }
+
+    /// Whether there is a (non-empty) path from `from` to `to`.
+    pub fn reaches(&self, from: &N, to: &N) -> bool {
+        match (self.index.get(from), self.index.get(to)) {
+            (Some(&i), Some(&j)) => self.get(i, j),
+            _ => false,
+        }
+    }
+
+    /// Every node reachable from `from`, in topological order.
+    pub fn reachable_from<'a>(&'a self, from: &N) -> impl Iterator<Item = &'a N> + 'a {
+        let i = self.index.get(from).copied();
+        (0..self.ids.len())
+            .filter(move |&j| i.is_some_and(|i| self.get(i, j)))
+            .map(|j| &self.ids[j])
+    }
+
+    /// All pairs `(a, b)` where `b` is reachable from `a`, i.e. the edges of the closure.
+    pub fn edges(&self) -> impl Iterator<Item = (&N, &N)> + '_ {
+        (0..self.ids.len()).flat_map(move |i| {
+            (0..self.ids.len())
+                .filter(move |&j| self.get(i, j))
+                .map(move |j| (&self.ids[i], &self.ids[j]))
+        })
+    }
+
+    fn get(&self, i: usize, j: usize) -> bool {
+        self.bits[i * self.words_per_row + j / 64] & (1 << (j % 64)) != 0
+    }
+
+    fn row(&self, i: usize) -> &[u64] {
+        &self.bits[i * self.words_per_row..(i + 1) * self.words_per_row]
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Node,
+{
+    /// Kahn's algorithm. Whenever several nodes are ready, the smallest id goes first, so the
+    /// order is deterministic.
+    pub fn topological_sort(&self) -> Result<Vec<T::NodeIdType>, CycleError<T::NodeIdType>> {
+        let mut in_degree: HashMap<&T::NodeIdType, usize> =
+            self.nodes.keys().map(|n| (n, 0)).collect();
+        for edges in self.adjacency_list.values() {
+            for e in edges {
+                *in_degree.entry(&e.node).or_default() += 1;
+            }
+        }
+        for n in self.adjacency_list.keys() {
+            in_degree.entry(n).or_default();
+        }
+
+        let mut ready: BinaryHeap<_> = in_degree
+            .iter()
+            .filter(|(_, d)| **d == 0)
+            .map(|(&n, _)| Reverse(n))
+            .collect();
+
+        let mut sorted = Vec::with_capacity(in_degree.len());
+        while let Some(Reverse(n)) = ready.pop() {
+            sorted.push(n.clone());
+            for e in self.adjacency_list.get(n).into_iter().flatten() {
+                let d = in_degree.get_mut(&e.node).unwrap();
+                *d -= 1;
+                if *d == 0 {
+                    ready.push(Reverse(&e.node));
+                }
+            }
+        }
+
+        if sorted.len() < in_degree.len() {
+            let node = in_degree
+                .into_iter()
+                .filter(|(_, d)| *d > 0)
+                .map(|(n, _)| n.clone())
+                .min()
+                .unwrap();
+            return Err(CycleError { node });
+        }
+
+        Ok(sorted)
+    }
+
+    /// Computes which nodes can reach which, see [`Reachability`].
+    pub fn transitive_closure(
+        &self,
+    ) -> Result<Reachability<T::NodeIdType>, CycleError<T::NodeIdType>> {
+        let ids = self.topological_sort()?;
+        let index: HashMap<_, _> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.clone(), i))
+            .collect();
+        let words_per_row = ids.len().div_ceil(64);
+        let mut bits = vec![0u64; ids.len() * words_per_row];
+
+        // in reverse topological order every successor's row is already complete
+        for (i, n) in ids.iter().enumerate().rev() {
+            for e in self.adjacency_list.get(n).into_iter().flatten() {
+                let j = index[&e.node];
+                bits[i * words_per_row + j / 64] |= 1 << (j % 64);
+
+                let (head, tail) = bits.split_at_mut(j * words_per_row);
+                let row_i = &mut head[i * words_per_row..(i + 1) * words_per_row];
+                for (a, b) in row_i.iter_mut().zip(&tail[..words_per_row]) {
+                    *a |= b;
+                }
+            }
+        }
+
+        Ok(Reachability {
+            ids,
+            index,
+            words_per_row,
+            bits,
+        })
+    }
+
+    /// The smallest subgraph with the same reachability as this DAG. Redundant edges (those
+    /// implied by a longer path) and duplicate edges are dropped, the remaining ones keep their
+    /// cost.
+    pub fn transitive_reduction(&self) -> Result<Graph<T>, CycleError<T::NodeIdType>> {
+        let closure = self.transitive_closure()?;
+
+        let mut adjacency_list: <Graph<T> as Graphable>::AdjacencyList = HashMap::new();
+        for (from, edges) in &self.adjacency_list {
+            // everything reachable through at least two edges
+            let mut indirect = vec![0u64; closure.words_per_row];
+            for e in edges {
+                for (a, b) in indirect.iter_mut().zip(closure.row(closure.index[&e.node])) {
+                    *a |= b;
+                }
+            }
+
+            let mut seen = HashSet::new();
+            let kept = edges
+                .iter()
+                .filter(|e| {
+                    let j = closure.index[&e.node];
+                    indirect[j / 64] & (1 << (j % 64)) == 0 && seen.insert(&e.node)
+                })
+                .cloned()
+                .collect();
+            adjacency_list.insert(from.clone(), kept);
+        }
+
+        Ok(Graph::new(self.nodes.clone(), adjacency_list))
+    }
+
+    /// For every node reachable from `sources`, the number of distinct paths leading to it from
+    /// any of them. A source counts as one (empty) path to itself, and parallel edges count as
+    /// separate paths.
+    pub fn path_counts(
+        &self,
+        sources: &[T::NodeIdType],
+    ) -> Result<HashMap<T::NodeIdType, usize>, CycleError<T::NodeIdType>> {
+        let order = self.topological_sort()?;
+        let sources: HashSet<_> = sources.iter().collect();
+
+        let mut counts = HashMap::new();
+        for n in order {
+            let count = counts.get(&n).copied().unwrap_or(0) + sources.contains(&n) as usize;
+            if count == 0 {
+                continue;
+            }
+            for e in self.adjacency_list.get(&n).into_iter().flatten() {
+                *counts.entry(e.node.clone()).or_default() += count;
+            }
+            counts.insert(n, count);
+        }
+
+        Ok(counts)
+    }
+
+    /// Number of distinct paths that start in one of `sources` and end in one of `sinks`, e.g.
+    /// the hiking trails from any trailhead to any peak.
+    pub fn count_paths(
+        &self,
+        sources: &[T::NodeIdType],
+        sinks: &[T::NodeIdType],
+    ) -> Result<usize, CycleError<T::NodeIdType>> {
+        let counts = self.path_counts(sources)?;
+        let sinks: HashSet<_> = sinks.iter().collect();
+        Ok(sinks.into_iter().filter_map(|n| counts.get(n)).sum())
+    }
+
+    /// The path with the highest total cost. Among equally heavy paths
+    /// the one ending first in topological order wins. Empty for an empty graph.
+    pub fn longest_path(&self) -> Result<WeightedPath<T::NodeIdType>, CycleError<T::NodeIdType>> {
+        let order = self.topological_sort()?;
+
+        // heaviest path ending in each node, and where it came from
+        let mut best: HashMap<&T::NodeIdType, (usize, Option<&T::NodeIdType>)> =
+            order.iter().map(|n| (n, (0, None))).collect();
+        for n in &order {
+            let cost = best[n].0;
+            for e in self.adjacency_list.get(n).into_iter().flatten() {
+                let entry = best.get_mut(&e.node).unwrap();
+                if cost + e.cost > entry.0 {
+                    *entry = (cost + e.cost, Some(n));
+                }
+            }
+        }
+
+        let Some(mut end) = order.iter().rev().max_by_key(|n| best[n].0) else {
+            return Ok(WeightedPath {
+                nodes: vec![],
+                cost: 0,
+            });
+        };
+        let cost = best[end].0;
+        let mut nodes = vec![end.clone()];
+        while let Some(prev) = best[end].1 {
+            nodes.push(prev.clone());
+            end = prev;
+        }
+        nodes.reverse();
+
+        Ok(WeightedPath { nodes, cost })
+    }
+
+    /// Lists the paths from `sources` to `sinks` (see [`Graph::count_paths`]), stopping after
+    /// `limit` of them. Sources are tried in the given order and edges in the order they were
+    /// added, so the result is deterministic.
+    pub fn paths(
+        &self,
+        sources: &[T::NodeIdType],
+        sinks: &[T::NodeIdType],
+        limit: usize,
+    ) -> Result<Vec<Vec<T::NodeIdType>>, CycleError<T::NodeIdType>> {
+        let order = self.topological_sort()?;
+        let sinks: HashSet<_> = sinks.iter().collect();
+
+        // skip every branch that can't end in a sink
+        let mut leads_to_sink = HashSet::new();
+        for n in order.iter().rev() {
+            let mut edges = self.adjacency_list.get(n).into_iter().flatten();
+            if sinks.contains(n) || edges.any(|e| leads_to_sink.contains(&e.node)) {
+                leads_to_sink.insert(n);
+            }
+        }
+
+        let mut paths = vec![];
+        for source in sources.iter().filter(|n| leads_to_sink.contains(n)) {
+            let mut path = vec![source];
+            let mut next_edge = vec![0];
+            while let Some(&n) = path.last() {
+                if paths.len() == limit {
+                    return Ok(paths);
+                }
+
+                let i = next_edge.last_mut().unwrap();
+                if *i == 0 && sinks.contains(n) {
+                    paths.push(path.iter().map(|&n| n.clone()).collect());
+                }
+
+                let edges = self.adjacency_list.get(n).map_or(&[][..], |edges| edges);
+                match edges[*i..]
+                    .iter()
+                    .position(|e| leads_to_sink.contains(&e.node))
+                {
+                    Some(offset) => {
+                        let e = &edges[*i + offset];
+                        *i += offset + 1;
+                        path.push(&e.node);
+                        next_edge.push(0);
+                    }
+                    None => {
+                        path.pop();
+                        next_edge.pop();
+                    }
+                }
+            }
+        }
+
+        Ok(paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_graphs::*;
+
+    fn sorted_edges(graph: &Graph<TestNode>) -> Vec<(usize, usize, usize)> {
+        let mut edges: Vec<_> = graph
+            .adjacency_list
+            .iter()
+            .flat_map(|(from, edges)| edges.iter().map(|e| (from.0, e.node.0, e.cost)))
+            .collect();
+        edges.sort();
+        edges
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let graph = create_test_graph(vec![(3, 1, 1), (1, 2, 1), (0, 2, 1), (3, 0, 1)]);
+        assert_eq!(graph.topological_sort(), Ok(ids(&[3, 0, 1, 2])));
+    }
+
+    #[test]
+    fn test_topological_sort_cycle() {
+        let graph = create_test_graph(vec![(0, 1, 1), (1, 2, 1), (2, 1, 1), (2, 3, 1)]);
+        assert_eq!(
+            graph.topological_sort(),
+            Err(CycleError {
+                node: TestNodeId(1)
+            })
+        );
+        assert!(graph.transitive_closure().is_err());
+    }
+
+    #[test]
+    fn test_transitive_closure() {
+        // page ordering rules: 47|53, 97|47, 97|61, 61|53, 75|47
+        let graph = create_test_graph(vec![
+            (47, 53, 1),
+            (97, 47, 1),
+            (97, 61, 1),
+            (61, 53, 1),
+            (75, 47, 1),
+        ]);
+        let closure = graph.transitive_closure().unwrap();
+
+        assert!(closure.reaches(&TestNodeId(97), &TestNodeId(53)));
+        assert!(closure.reaches(&TestNodeId(75), &TestNodeId(53)));
+        assert!(!closure.reaches(&TestNodeId(53), &TestNodeId(97)));
+        assert!(!closure.reaches(&TestNodeId(75), &TestNodeId(61)));
+        assert!(!closure.reaches(&TestNodeId(47), &TestNodeId(47)));
+        assert!(!closure.reaches(&TestNodeId(47), &TestNodeId(1000)));
+
+        let mut from_97: Vec<_> = closure.reachable_from(&TestNodeId(97)).cloned().collect();
+        from_97.sort();
+        assert_eq!(from_97, ids(&[47, 53, 61]));
+        assert_eq!(closure.edges().count(), 7);
+    }
+
+    #[test]
+    fn test_transitive_closure_wide() {
+        // a chain longer than one bitset word
+        let graph = create_test_graph((0..150).map(|i| (i, i + 1, 1)).collect());
+        let closure = graph.transitive_closure().unwrap();
+
+        assert!(closure.reaches(&TestNodeId(0), &TestNodeId(150)));
+        assert!(closure.reaches(&TestNodeId(70), &TestNodeId(130)));
+        assert!(!closure.reaches(&TestNodeId(130), &TestNodeId(70)));
+        assert_eq!(closure.reachable_from(&TestNodeId(100)).count(), 50);
+    }
+
+    #[test]
+    fn test_transitive_reduction() {
+        let graph = create_test_graph(vec![
+            (0, 1, 1),
+            (1, 2, 2),
+            (0, 2, 3), // implied by 0->1->2
+            (2, 3, 4),
+            (0, 3, 5), // implied by 0->1->2->3
+            (1, 3, 6), // implied by 1->2->3
+            (0, 4, 7),
+            (0, 4, 8), // duplicate
+        ]);
+        let reduction = graph.transitive_reduction().unwrap();
+
+        assert_eq!(
+            sorted_edges(&reduction),
+            vec![(0, 1, 1), (0, 4, 7), (1, 2, 2), (2, 3, 4)]
+        );
+    }
+
+    #[test]
+    fn test_count_paths() {
+        // a diamond 0 -> {1, 2} -> 3 with a parallel edge 2 -> 3, plus a dead end 1 -> 4
+        let graph = create_test_graph(vec![
+            (0, 1, 1),
+            (0, 2, 1),
+            (1, 3, 1),
+            (2, 3, 1),
+            (2, 3, 1),
+            (1, 4, 1),
+            (5, 3, 1),
+        ]);
+
+        let counts = graph.path_counts(&ids(&[0])).unwrap();
+        assert_eq!(counts[&TestNodeId(3)], 3);
+        assert_eq!(counts[&TestNodeId(4)], 1);
+        assert_eq!(counts[&TestNodeId(0)], 1);
+        assert!(!counts.contains_key(&TestNodeId(5)));
+
+        assert_eq!(graph.count_paths(&ids(&[0]), &ids(&[3, 4])), Ok(4));
+        assert_eq!(graph.count_paths(&ids(&[0, 5]), &ids(&[3])), Ok(4));
+        assert_eq!(graph.count_paths(&ids(&[3]), &ids(&[0])), Ok(0));
+
+        let cyclic = create_test_graph(vec![(0, 1, 1), (1, 0, 1)]);
+        assert!(cyclic.count_paths(&ids(&[0]), &ids(&[1])).is_err());
+    }
+
+    #[test]
+    fn test_longest_path() {
+        let graph = create_test_graph(vec![
+            (0, 1, 2),
+            (1, 3, 2),
+            (0, 2, 1),
+            (2, 3, 5),
+            (3, 4, 1),
+            (5, 4, 3),
+        ]);
+        assert_eq!(
+            graph.longest_path(),
+            Ok(WeightedPath {
+                nodes: ids(&[0, 2, 3, 4]),
+                cost: 7
+            })
+        );
+
+        let empty = create_test_graph(vec![]).longest_path().unwrap();
+        assert!(empty.nodes.is_empty());
+    }
+
+    #[test]
+    fn test_paths() {
+        let graph = create_test_graph(vec![
+            (0, 1, 1),
+            (0, 2, 1),
+            (1, 3, 1),
+            (2, 3, 1),
+            (1, 4, 1),
+            (3, 5, 1),
+        ]);
+
+        let paths = graph.paths(&ids(&[0]), &ids(&[3, 5]), usize::MAX).unwrap();
+        assert_eq!(
+            paths,
+            vec![
+                ids(&[0, 1, 3]),
+                ids(&[0, 1, 3, 5]),
+                ids(&[0, 2, 3]),
+                ids(&[0, 2, 3, 5])
+            ]
+        );
+        assert_eq!(
+            paths.len(),
+            graph.count_paths(&ids(&[0]), &ids(&[3, 5])).unwrap()
+        );
+
+        let first_two = graph.paths(&ids(&[0]), &ids(&[3, 5]), 2).unwrap();
+        assert_eq!(first_two, paths[..2]);
+        assert_eq!(graph.paths(&ids(&[4]), &ids(&[5]), 10), Ok(vec![]));
+    }
+}
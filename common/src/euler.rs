@@ -0,0 +1,356 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+
+use crate::{Graph, Node};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EulerError<N> {
+    /// the degrees of these nodes rule out an eulerian path / circuit. For directed graphs these
+    /// are the nodes with in-degree != out-degree, for undirected ones the nodes of odd degree.
+    Imbalanced { nodes: Vec<N> },
+    /// the degrees work out, but the edges are not all connected to each other
+    Disconnected,
+}
+
+impl<N> Display for EulerError<N>
+where
+    N: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EulerError::Imbalanced { nodes } => write!(f, "imbalanced degrees at {nodes:?}"),
+            EulerError::Disconnected => write!(f, "edges are not connected"),
+        }
+    }
+}
+
+impl<N> std::error::Error for EulerError<N> where N: Debug {}
+
+impl<T> Graph<T>
+where
+    T: Node,
+{
+    /// A walk using every edge exactly once (parallel edges and self-loops included), as the list
+    /// of nodes visited. Returns an empty walk for a graph without edges.
+    pub fn eulerian_path(&self) -> Result<Vec<T::NodeIdType>, EulerError<T::NodeIdType>> {
+        self.directed_euler(false)
+    }
+
+    /// Like [`Graph::eulerian_path`], but the walk has to end where it started.
+    pub fn eulerian_circuit(&self) -> Result<Vec<T::NodeIdType>, EulerError<T::NodeIdType>> {
+        self.directed_euler(true)
+    }
+
+    fn directed_euler(
+        &self,
+        circuit: bool,
+    ) -> Result<Vec<T::NodeIdType>, EulerError<T::NodeIdType>> {
+        let indexed = self.indexed();
+        let n = indexed.len();
+
+        let mut balance = vec![0i64; n]; // out - in
+        for (from, edges) in indexed.adj.iter().enumerate() {
+            for &(to, _) in edges {
+                balance[from] += 1;
+                balance[to] -= 1;
+            }
+        }
+
+        let imbalanced: Vec<_> = (0..n).filter(|&i| balance[i] != 0).collect();
+        let starts: Vec<_> = imbalanced.iter().filter(|&&i| balance[i] == 1).collect();
+        let ends: Vec<_> = imbalanced.iter().filter(|&&i| balance[i] == -1).collect();
+        let is_path = imbalanced.len() == 2 && starts.len() == 1 && ends.len() == 1;
+        if !imbalanced.is_empty() && (circuit || !is_path) {
+            return Err(EulerError::Imbalanced {
+                nodes: imbalanced.iter().map(|&i| indexed.ids[i].clone()).collect(),
+            });
+        }
+
+        let num_edges: usize = indexed.adj.iter().map(|edges| edges.len()).sum();
+        let start = match starts.first() {
+            Some(&&start) => start,
+            None => match (0..n).find(|&i| !indexed.adj[i].is_empty()) {
+                Some(start) => start,
+                None => return Ok(vec![]),
+            },
+        };
+
+        // hierholzer: walk until stuck, then back up and splice in detours
+        let mut next_edge = vec![0; n];
+        let mut stack = vec![start];
+        let mut walk = Vec::with_capacity(num_edges + 1);
+        while let Some(&u) = stack.last() {
+            if let Some(&(v, _)) = indexed.adj[u].get(next_edge[u]) {
+                next_edge[u] += 1;
+                stack.push(v);
+            } else {
+                walk.push(stack.pop().unwrap());
+            }
+        }
+
+        if walk.len() != num_edges + 1 {
+            return Err(EulerError::Disconnected);
+        }
+
+        walk.reverse();
+        Ok(walk.into_iter().map(|i| indexed.ids[i].clone()).collect())
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Node,
+{
+    /// A walk using every edge exactly once, treating edges as undirected. Parallel edges and
+    /// self-loops count, while an edge stored in both directions (`a -> b` and `b -> a`) is a
+    /// single undirected edge. Returns an empty walk for a graph without edges.
+    pub fn undirected_eulerian_path(
+        &self,
+    ) -> Result<Vec<T::NodeIdType>, EulerError<T::NodeIdType>> {
+        self.undirected_euler(false)
+    }
+
+    /// Like [`Graph::undirected_eulerian_path`], but the walk has to end where it started.
+    pub fn undirected_eulerian_circuit(
+        &self,
+    ) -> Result<Vec<T::NodeIdType>, EulerError<T::NodeIdType>> {
+        self.undirected_euler(true)
+    }
+
+    fn undirected_euler(
+        &self,
+        circuit: bool,
+    ) -> Result<Vec<T::NodeIdType>, EulerError<T::NodeIdType>> {
+        let indexed = self.indexed();
+        let n = indexed.len();
+
+        // number every edge so it can be marked as used from both ends. `b -> a` is paired up
+        // with an earlier unpaired `a -> b` instead of becoming a second edge.
+        let mut edge_ids = vec![vec![]; n];
+        let mut degree = vec![0; n];
+        let mut unpaired: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut num_edges = 0;
+        for (i, edges) in indexed.adj.iter().enumerate() {
+            for &(j, _) in edges {
+                if i != j {
+                    if let Some(count) = unpaired.get_mut(&(j, i)).filter(|c| **c > 0) {
+                        *count -= 1;
+                        continue;
+                    }
+                    *unpaired.entry((i, j)).or_default() += 1;
+                    edge_ids[j].push((i, num_edges));
+                }
+                edge_ids[i].push((j, num_edges));
+                // a self-loop adds 2 to the degree of its node
+                degree[i] += 1;
+                degree[j] += 1;
+                num_edges += 1;
+            }
+        }
+        for edges in edge_ids.iter_mut() {
+            edges.sort();
+        }
+
+        let odd: Vec<_> = (0..n).filter(|&i| degree[i] % 2 == 1).collect();
+        if (circuit && !odd.is_empty()) || odd.len() > 2 {
+            return Err(EulerError::Imbalanced {
+                nodes: odd.iter().map(|&i| indexed.ids[i].clone()).collect(),
+            });
+        }
+
+        let start = match odd.first() {
+            Some(&start) => start,
+            None => match (0..n).find(|&i| !edge_ids[i].is_empty()) {
+                Some(start) => start,
+                None => return Ok(vec![]),
+            },
+        };
+
+        let mut used = vec![false; num_edges];
+        let mut next_edge = vec![0; n];
+        let mut stack = vec![start];
+        let mut walk = Vec::with_capacity(num_edges + 1);
+        while let Some(&u) = stack.last() {
+            while next_edge[u] < edge_ids[u].len() && used[edge_ids[u][next_edge[u]].1] {
+                next_edge[u] += 1;
+            }
+
+            if let Some(&(v, edge)) = edge_ids[u].get(next_edge[u]) {
+                used[edge] = true;
+                stack.push(v);
+            } else {
+                walk.push(stack.pop().unwrap());
+            }
+        }
+
+        if walk.len() != num_edges + 1 {
+            return Err(EulerError::Disconnected);
+        }
+
+        walk.reverse();
+        Ok(walk.into_iter().map(|i| indexed.ids[i].clone()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_graphs::*;
+
+    // checks that `walk` uses every edge of `edges` exactly once
+    fn assert_uses_all_edges(walk: &[TestNodeId], edges: &[(usize, usize, usize)], directed: bool) {
+        let mut expected: Vec<_> = edges
+            .iter()
+            .map(|&(a, b, _)| if directed || a < b { (a, b) } else { (b, a) })
+            .collect();
+        let mut actual: Vec<_> = walk
+            .windows(2)
+            .map(|w| (w[0].0, w[1].0))
+            .map(|(a, b)| if directed || a < b { (a, b) } else { (b, a) })
+            .collect();
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_directed_circuit() {
+        let edges = vec![
+            (0, 1, 1),
+            (1, 2, 1),
+            (2, 0, 1),
+            (0, 3, 1),
+            (3, 0, 1),
+            (1, 1, 1),
+        ];
+        let graph = create_test_graph(edges.clone());
+
+        let walk = graph.eulerian_circuit().unwrap();
+        assert_eq!(walk.first(), walk.last());
+        assert_uses_all_edges(&walk, &edges, true);
+    }
+
+    #[test]
+    fn test_directed_path() {
+        // 2 has one more outgoing edge, 3 one more incoming edge
+        let edges = vec![(0, 1, 1), (1, 0, 1), (2, 0, 1), (1, 3, 1), (0, 1, 1)];
+        let graph = create_test_graph(edges.clone());
+
+        let walk = graph.eulerian_path().unwrap();
+        assert_eq!(walk.first(), Some(&TestNodeId(2)));
+        assert_eq!(walk.last(), Some(&TestNodeId(3)));
+        assert_uses_all_edges(&walk, &edges, true);
+
+        assert_eq!(
+            graph.eulerian_circuit(),
+            Err(EulerError::Imbalanced {
+                nodes: ids(&[2, 3])
+            })
+        );
+    }
+
+    #[test]
+    fn test_directed_errors() {
+        let graph = create_test_graph(vec![(0, 1, 1), (0, 2, 1), (0, 3, 1)]);
+        assert_eq!(
+            graph.eulerian_path(),
+            Err(EulerError::Imbalanced {
+                nodes: ids(&[0, 1, 2, 3])
+            })
+        );
+
+        let graph = create_test_graph(vec![(0, 1, 1), (1, 0, 1), (2, 3, 1), (3, 2, 1)]);
+        assert_eq!(graph.eulerian_circuit(), Err(EulerError::Disconnected));
+    }
+
+    #[test]
+    fn test_undirected_path_and_circuit() {
+        // the "house of Santa Claus": only 0 and 1 have odd degree
+        let edges = vec![
+            (0, 1, 1),
+            (0, 2, 1),
+            (0, 3, 1),
+            (1, 2, 1),
+            (1, 3, 1),
+            (2, 3, 1),
+            (2, 4, 1),
+            (3, 4, 1),
+        ];
+        let graph = create_test_graph(edges.clone());
+
+        let walk = graph.undirected_eulerian_path().unwrap();
+        assert_eq!(walk.first(), Some(&TestNodeId(0)));
+        assert_eq!(walk.last(), Some(&TestNodeId(1)));
+        assert_uses_all_edges(&walk, &edges, false);
+
+        assert_eq!(
+            graph.undirected_eulerian_circuit(),
+            Err(EulerError::Imbalanced {
+                nodes: ids(&[0, 1])
+            })
+        );
+
+        // closing the gap makes a circuit possible
+        let mut edges = edges;
+        edges.retain(|&(a, b, _)| (a, b) != (0, 1));
+        let graph = create_test_graph(edges.clone());
+        let walk = graph.undirected_eulerian_circuit().unwrap();
+        assert_eq!(walk.first(), walk.last());
+        assert_uses_all_edges(&walk, &edges, false);
+    }
+
+    #[test]
+    fn test_no_edges() {
+        let graph = create_test_graph(vec![]);
+        assert_eq!(graph.eulerian_circuit(), Ok(vec![]));
+        assert_eq!(graph.undirected_eulerian_path(), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_undirected_multigraph() {
+        // parallel edges are separate edges, the walk has to cross 0-1 twice
+        let edges = vec![(0, 1, 1), (0, 1, 1), (1, 2, 1), (2, 0, 1)];
+        let walk = create_test_graph(edges.clone())
+            .undirected_eulerian_path()
+            .unwrap();
+        assert_eq!(
+            (walk.first(), walk.last()),
+            (Some(&TestNodeId(0)), Some(&TestNodeId(1)))
+        );
+        assert_uses_all_edges(&walk, &edges, false);
+
+        // the bridges of Königsberg: 0 is the island, 1 and 2 the river banks, 3 the east side
+        let mut bridges = vec![
+            (0, 1, 1),
+            (0, 1, 1),
+            (0, 2, 1),
+            (0, 2, 1),
+            (0, 3, 1),
+            (1, 3, 1),
+            (2, 3, 1),
+        ];
+        assert_eq!(
+            create_test_graph(bridges.clone()).undirected_eulerian_path(),
+            Err(EulerError::Imbalanced {
+                nodes: ids(&[0, 1, 2, 3])
+            })
+        );
+
+        // an eighth bridge between the banks and a self-loop on the island leave 0 and 3 odd
+        bridges.extend([(1, 2, 1), (0, 0, 1)]);
+        let walk = create_test_graph(bridges.clone())
+            .undirected_eulerian_path()
+            .unwrap();
+        assert_eq!(
+            (walk.first(), walk.last()),
+            (Some(&TestNodeId(0)), Some(&TestNodeId(3)))
+        );
+        assert_uses_all_edges(&walk, &bridges, false);
+    }
+
+    #[test]
+    fn test_undirected_edges_stored_both_ways() {
+        let graph = create_test_graph(vec![(0, 1, 1), (1, 0, 1), (1, 2, 1), (2, 1, 1)]);
+        assert_eq!(graph.undirected_eulerian_path(), Ok(ids(&[0, 1, 2])));
+    }
+}
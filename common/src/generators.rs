@@ -0,0 +1,355 @@
+use std::ops::RangeInclusive;
+
+use crate::{Graph, PlainNode};
+
+/// Small, seedable pseudo random number generator (SplitMix64). Good enough for generating test
+/// inputs, and the same seed always gives the same sequence on every platform.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// uniform in `[0, 1)`
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// uniform in `0..n`, `n` must not be 0
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+        range.start() + self.below(range.end() - range.start() + 1)
+    }
+
+    pub fn chance(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+}
+
+/// Erdős–Rényi graph on the nodes `0..n`: every possible edge exists with probability `p`, and
+/// gets a cost picked uniformly from `costs`. Undirected graphs store each edge in both
+/// directions (with the same cost).
+pub fn erdos_renyi(
+    n: usize,
+    p: f64,
+    directed: bool,
+    costs: RangeInclusive<usize>,
+    seed: u64,
+) -> Graph<PlainNode<usize>> {
+    let mut rng = SplitMix64::new(seed);
+    let mut edges = vec![];
+    for a in 0..n {
+        let targets = if directed { 0..n } else { a + 1..n };
+        for b in targets.filter(|&b| b != a) {
+            if rng.chance(p) {
+                let cost = rng.range(costs.clone());
+                edges.push((a, b, cost));
+                if !directed {
+                    edges.push((b, a, cost));
+                }
+            }
+        }
+    }
+
+    Graph::from_edges(0..n, edges)
+}
+
+/// 4-connected `width` x `height` grid with `(col, row)` node ids. Every step between
+/// neighbouring cells gets its own cost from `costs`, so the two directions may differ.
+pub fn grid_graph(
+    width: usize,
+    height: usize,
+    costs: RangeInclusive<usize>,
+    seed: u64,
+) -> Graph<PlainNode<(usize, usize)>> {
+    let mut rng = SplitMix64::new(seed);
+    let mut edges = vec![];
+    for row in 0..height {
+        for col in 0..width {
+            if col + 1 < width {
+                edges.push(((col, row), (col + 1, row), rng.range(costs.clone())));
+                edges.push(((col + 1, row), (col, row), rng.range(costs.clone())));
+            }
+            if row + 1 < height {
+                edges.push(((col, row), (col, row + 1), rng.range(costs.clone())));
+                edges.push(((col, row + 1), (col, row), rng.range(costs.clone())));
+            }
+        }
+    }
+
+    let nodes = (0..height).flat_map(|row| (0..width).map(move |col| (col, row)));
+    Graph::from_edges(nodes, edges)
+}
+
+/// A maze with exactly one path between any two cells, see [`braided_maze`].
+pub fn perfect_maze(cells_wide: usize, cells_high: usize, seed: u64) -> String {
+    braided_maze(cells_wide, cells_high, 0.0, seed)
+}
+
+/// A maze in the 2024 day 16 format: `#` walls, `.` floor, `S` in the bottom-left corner and `E`
+/// in the top-right one, surrounded by a wall.
+///
+/// The maze has `cells_wide` x `cells_high` cells, so the text is `2 * cells_wide + 1` columns by
+/// `2 * cells_high + 1` rows. It needs at least two cells, so that `S` and `E` don't overlap. The
+/// maze starts out as a perfect maze (a recursive backtracker), and then every dead end is opened
+/// up into a loop with probability `braid`.
+pub fn braided_maze(cells_wide: usize, cells_high: usize, braid: f64, seed: u64) -> String {
+    assert!(
+        cells_wide * cells_high >= 2,
+        "a maze needs at least two cells"
+    );
+
+    let mut rng = SplitMix64::new(seed);
+    let (width, height) = (2 * cells_wide + 1, 2 * cells_high + 1);
+    let mut map = vec![vec!['#'; width]; height];
+    let center = |(cx, cy): (usize, usize)| (2 * cx + 1, 2 * cy + 1);
+    let neighbors = |(cx, cy): (usize, usize)| {
+        let mut cells = vec![];
+        if cx > 0 {
+            cells.push((cx - 1, cy));
+        }
+        if cx + 1 < cells_wide {
+            cells.push((cx + 1, cy));
+        }
+        if cy > 0 {
+            cells.push((cx, cy - 1));
+        }
+        if cy + 1 < cells_high {
+            cells.push((cx, cy + 1));
+        }
+        cells
+    };
+
+    // carve passages with a randomized depth-first search
+    let start = (0, cells_high - 1);
+    let mut visited = vec![vec![false; cells_wide]; cells_high];
+    visited[start.1][start.0] = true;
+    let (x, y) = center(start);
+    map[y][x] = '.';
+
+    let mut stack = vec![start];
+    while let Some(&cell) = stack.last() {
+        let unvisited: Vec<_> = neighbors(cell)
+            .into_iter()
+            .filter(|&(cx, cy)| !visited[cy][cx])
+            .collect();
+        if unvisited.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        let next = unvisited[rng.below(unvisited.len())];
+        visited[next.1][next.0] = true;
+        let ((x1, y1), (x2, y2)) = (center(cell), center(next));
+        map[(y1 + y2) / 2][(x1 + x2) / 2] = '.';
+        map[y2][x2] = '.';
+        stack.push(next);
+    }
+
+    // braid: knock a wall out of dead ends
+    for cy in 0..cells_high {
+        for cx in 0..cells_wide {
+            let (x, y) = center((cx, cy));
+            let walls: Vec<_> = neighbors((cx, cy))
+                .into_iter()
+                .map(|n| {
+                    let (nx, ny) = center(n);
+                    ((x + nx) / 2, (y + ny) / 2)
+                })
+                .filter(|&(wx, wy)| map[wy][wx] == '#')
+                .collect();
+            let open = neighbors((cx, cy)).len() - walls.len();
+
+            if open == 1 && !walls.is_empty() && rng.chance(braid) {
+                let (wx, wy) = walls[rng.below(walls.len())];
+                map[wy][wx] = '.';
+            }
+        }
+    }
+
+    map[height - 2][1] = 'S';
+    map[1][width - 2] = 'E';
+
+    map.into_iter()
+        .map(|line| line.into_iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    // bellman-ford over the raw edge list, slow but obviously correct
+    fn brute_force_distances(
+        graph: &Graph<PlainNode<usize>>,
+        n: usize,
+        start: usize,
+    ) -> Vec<usize> {
+        let mut dist = vec![usize::MAX; n];
+        dist[start] = 0;
+        for _ in 0..n {
+            for (from, edges) in &graph.adjacency_list {
+                for e in edges {
+                    if dist[*from] != usize::MAX {
+                        dist[e.node] = dist[e.node].min(dist[*from] + e.cost);
+                    }
+                }
+            }
+        }
+        dist
+    }
+
+    fn open_cells(maze: &str) -> HashMap<(usize, usize), char> {
+        maze.lines()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c != '#')
+                    .map(move |(col, c)| ((col, row), c))
+            })
+            .collect()
+    }
+
+    fn open_neighbors(cells: &HashMap<(usize, usize), char>, (col, row): (usize, usize)) -> usize {
+        [
+            (col + 1, row),
+            (col - 1, row),
+            (col, row + 1),
+            (col, row - 1),
+        ]
+        .iter()
+        .filter(|p| cells.contains_key(p))
+        .count()
+    }
+
+    #[test]
+    fn test_rng_is_deterministic() {
+        let a: Vec<_> = (0..5)
+            .scan(SplitMix64::new(42), |rng, _| Some(rng.next_u64()))
+            .collect();
+        let b: Vec<_> = (0..5)
+            .scan(SplitMix64::new(42), |rng, _| Some(rng.next_u64()))
+            .collect();
+        assert_eq!(a, b);
+
+        let mut rng = SplitMix64::new(7);
+        assert!((0..1000).all(|_| (3..=5).contains(&rng.range(3..=5))));
+    }
+
+    #[test]
+    fn test_erdos_renyi() {
+        let graph = erdos_renyi(50, 0.1, false, 1..=9, 1);
+        let edges: Vec<_> = graph
+            .adjacency_list
+            .iter()
+            .flat_map(|(a, edges)| edges.iter().map(move |e| (*a, e.node, e.cost)))
+            .collect();
+
+        // undirected edges come in symmetric pairs
+        for &(a, b, cost) in &edges {
+            assert!(edges.contains(&(b, a, cost)));
+            assert!((1..=9).contains(&cost));
+        }
+        // 1225 possible edges, so ~122 expected
+        assert!((80..170).contains(&(edges.len() / 2)));
+
+        assert_eq!(graph.nodes.len(), 50);
+        let again = erdos_renyi(50, 0.1, false, 1..=9, 1);
+        assert_eq!(again.dijkstra(&0, None).dist, graph.dijkstra(&0, None).dist);
+    }
+
+    #[test]
+    fn test_grid_graph() {
+        let graph = grid_graph(4, 3, 1..=1, 0);
+        assert_eq!(graph.nodes.len(), 12);
+        assert_eq!(
+            graph.adjacency_list.values().flatten().count(),
+            2 * (3 * 3 + 4 * 2)
+        );
+
+        let result = graph.dijkstra(&(0, 0), None);
+        assert_eq!(result.dist[&(3, 2)], 5);
+    }
+
+    #[test]
+    fn test_perfect_maze() {
+        let maze = perfect_maze(8, 5, 3);
+        let lines: Vec<_> = maze.lines().collect();
+        assert_eq!(lines.len(), 11);
+        assert!(lines.iter().all(|l| l.len() == 17));
+        assert!(lines[0].chars().all(|c| c == '#'));
+        assert_eq!(lines[9].chars().nth(1), Some('S'));
+        assert_eq!(lines[1].chars().nth(15), Some('E'));
+
+        // a spanning tree: one less passage than there are open cells
+        let cells = open_cells(&maze);
+        let passages: usize = cells
+            .keys()
+            .map(|&p| open_neighbors(&cells, p))
+            .sum::<usize>()
+            / 2;
+        assert_eq!(passages + 1, cells.len());
+
+        assert_eq!(maze, perfect_maze(8, 5, 3));
+        assert_ne!(maze, perfect_maze(8, 5, 4));
+    }
+
+    #[test]
+    fn test_smallest_mazes() {
+        assert_eq!(perfect_maze(2, 1, 0), "#####\n#S.E#\n#####");
+        assert_eq!(perfect_maze(1, 2, 0), "###\n#E#\n#.#\n#S#\n###");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_single_cell_maze() {
+        perfect_maze(1, 1, 0);
+    }
+
+    #[test]
+    fn test_braided_maze() {
+        let maze = braided_maze(10, 10, 1.0, 11);
+        let cells = open_cells(&maze);
+
+        // no cell centre is a dead end anymore
+        let dead_ends = cells
+            .keys()
+            .filter(|(col, row)| col % 2 == 1 && row % 2 == 1)
+            .filter(|&&p| open_neighbors(&cells, p) == 1)
+            .count();
+        assert_eq!(dead_ends, 0);
+    }
+
+    #[test]
+    fn test_dijkstra_matches_brute_force() {
+        for seed in 0..20 {
+            let n = 30;
+            let graph = erdos_renyi(n, 0.08, true, 1..=20, seed);
+            let expected = brute_force_distances(&graph, n, 0);
+
+            let result = graph.dijkstra(&0, None);
+            for (node, expected) in expected.into_iter().enumerate() {
+                assert_eq!(result.dist[&node], expected, "seed {seed}, node {node}");
+            }
+        }
+    }
+}
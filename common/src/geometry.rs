@@ -0,0 +1,361 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::IdTrait;
+
+/// A position on an unbounded 2d lattice. `y` grows downwards, like the rows of a [`crate::Grid`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// The difference between two [`Point`]s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl IdTrait for Point {}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> i64 {
+        (other - self).manhattan_len()
+    }
+
+    pub fn chebyshev(self, other: Point) -> i64 {
+        (other - self).chebyshev_len()
+    }
+
+    /// Every lattice point on the segment from `self` to `to`, both ends included.
+    pub fn line_to(self, to: Point) -> impl Iterator<Item = Point> {
+        let step = (to - self).reduced();
+        let steps = match step {
+            Vector::ZERO => 0,
+            _ => (to - self).chebyshev_len() / step.chebyshev_len(),
+        };
+        (0..=steps).map(move |i| self + step * i)
+    }
+
+    /// The `(col, row)` grid cell at this point, `None` if either coordinate is negative.
+    pub fn cell(self) -> Option<(usize, usize)> {
+        Some((self.x.try_into().ok()?, self.y.try_into().ok()?))
+    }
+}
+
+impl Vector {
+    pub const ZERO: Vector = Vector { x: 0, y: 0 };
+
+    /// Steps to the 4 orthogonal neighbours, clockwise starting north.
+    pub const NEIGHBORS4: [Vector; 4] = [
+        Vector::new(0, -1),
+        Vector::new(1, 0),
+        Vector::new(0, 1),
+        Vector::new(-1, 0),
+    ];
+
+    /// Steps to all 8 surrounding cells, clockwise starting north.
+    pub const NEIGHBORS8: [Vector; 8] = [
+        Vector::new(0, -1),
+        Vector::new(1, -1),
+        Vector::new(1, 0),
+        Vector::new(1, 1),
+        Vector::new(0, 1),
+        Vector::new(-1, 1),
+        Vector::new(-1, 0),
+        Vector::new(-1, -1),
+    ];
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// The smallest vector pointing the same way whose multiples still hit every lattice point
+    /// this one passes through, e.g. `(4, -6)` becomes `(2, -3)`.
+    pub fn reduced(self) -> Vector {
+        match gcd(self.x.unsigned_abs(), self.y.unsigned_abs()) {
+            0 => self,
+            g => Vector::new(self.x / g as i64, self.y / g as i64),
+        }
+    }
+
+    pub fn manhattan_len(self) -> i64 {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn chebyshev_len(self) -> i64 {
+        self.x.abs().max(self.y.abs())
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((col, row): (usize, usize)) -> Self {
+        Self::new(col as i64, row as i64)
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<(i64, i64)> for Vector {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, v: Vector) -> Point {
+        Point::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, v: Vector) -> Point {
+        Point::new(self.x - v.x, self.y - v.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, v: Vector) {
+        *self = *self + v;
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, v: Vector) {
+        *self = *self - v;
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, k: i64) -> Vector {
+        Vector::new(self.x * k, self.y * k)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// One of the four grid directions, in clockwise order starting at north (up).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl IdTrait for Direction {}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Parses `^`, `>`, `v` and `<`.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Direction::North),
+            '>' => Some(Direction::East),
+            'v' => Some(Direction::South),
+            '<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    /// Parses `N`, `E`, `S` and `W`.
+    pub fn from_compass(c: char) -> Option<Self> {
+        match c {
+            'N' => Some(Direction::North),
+            'E' => Some(Direction::East),
+            'S' => Some(Direction::South),
+            'W' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
+
+    /// One step in this direction.
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction::North => Vector::new(0, -1),
+            Direction::East => Vector::new(1, 0),
+            Direction::South => Vector::new(0, 1),
+            Direction::West => Vector::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn turn_around(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+impl Mul<i64> for Direction {
+    type Output = Vector;
+
+    fn mul(self, k: i64) -> Vector {
+        self.vector() * k
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, d: Direction) -> Point {
+        self + d.vector()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(2, 3);
+        let b = Point::new(-1, 7);
+        let v = b - a;
+        assert_eq!(v, Vector::new(-3, 4));
+        assert_eq!(a + v, b);
+        assert_eq!(b - v, a);
+        assert_eq!(a + v * 2 - v, b);
+        assert_eq!(-v + v, Vector::ZERO);
+
+        let mut p = Point::ORIGIN;
+        p += Vector::new(1, 1);
+        p -= Vector::new(0, 3);
+        assert_eq!(p, Point::new(1, -2));
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point::new(1, 1);
+        let b = Point::new(4, -3);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(b.manhattan(a), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(Vector::new(-2, 0).chebyshev_len(), 2);
+    }
+
+    #[test]
+    fn test_cells() {
+        assert_eq!(Point::from((3usize, 4usize)), Point::new(3, 4));
+        assert_eq!(Point::new(3, 4).cell(), Some((3, 4)));
+        assert_eq!(Point::new(-1, 4).cell(), None);
+    }
+
+    #[test]
+    fn test_directions() {
+        let d = Direction::from_arrow('>').unwrap();
+        assert_eq!(d, Direction::East);
+        assert_eq!(Direction::from_compass('E'), Some(d));
+        assert_eq!(Direction::from_arrow('x'), None);
+
+        assert_eq!(d.turn_right(), Direction::South);
+        assert_eq!(d.turn_left(), Direction::North);
+        assert_eq!(d.turn_around(), Direction::West);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+        assert!(
+            Direction::ALL
+                .iter()
+                .all(|d| d.turn_left().turn_right() == *d)
+        );
+        assert_eq!(
+            Direction::ALL.map(|d| d.arrow()).iter().collect::<String>(),
+            "^>v<"
+        );
+
+        // north is up, towards the first row
+        assert_eq!(Point::new(5, 5) + Direction::North, Point::new(5, 4));
+        assert_eq!(Point::ORIGIN + Direction::West * 3, Point::new(-3, 0));
+    }
+
+    #[test]
+    fn test_lattice_lines() {
+        assert_eq!(Vector::new(4, -6).reduced(), Vector::new(2, -3));
+        assert_eq!(Vector::new(0, -5).reduced(), Vector::new(0, -1));
+        assert_eq!(Vector::ZERO.reduced(), Vector::ZERO);
+
+        let line: Vec<_> = Point::new(1, 1).line_to(Point::new(7, -8)).collect();
+        assert_eq!(
+            line,
+            vec![
+                Point::new(1, 1),
+                Point::new(3, -2),
+                Point::new(5, -5),
+                Point::new(7, -8)
+            ]
+        );
+        assert_eq!(Point::new(2, 2).line_to(Point::new(2, 2)).count(), 1);
+    }
+}
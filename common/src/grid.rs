@@ -0,0 +1,714 @@
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::{Point, Vector};
+
+/// Rectangular map stored row by row. Cells are addressed as `(col, row)`, like the node ids of
+/// [`crate::grid_graph`], with `(0, 0)` in the top-left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// Returned when text can't be turned into a [`Grid`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError {
+    /// there was not a single row
+    Empty,
+    /// `row` has a different length than the first one
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// the cell mapping rejected `cell`
+    InvalidCell { col: usize, row: usize, cell: char },
+}
+
+impl Display for GridParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridParseError::Empty => write!(f, "grid has no rows"),
+            GridParseError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {row} is {found} cells wide, but the grid is {expected} wide"
+            ),
+            GridParseError::InvalidCell { col, row, cell } => {
+                write!(f, "invalid cell {cell:?} at column {col}, row {row}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridParseError {}
+
+/// Types that a single map character can be parsed into, used by `Grid`'s [`FromStr`].
+pub trait FromCell: Sized {
+    fn from_cell(c: char) -> Option<Self>;
+}
+
+impl FromCell for char {
+    fn from_cell(c: char) -> Option<Self> {
+        Some(c)
+    }
+}
+
+/// decimal digits, e.g. heights on a topographic map
+impl FromCell for u8 {
+    fn from_cell(c: char) -> Option<Self> {
+        c.to_digit(10).map(|d| d as u8)
+    }
+}
+
+impl<T> FromStr for Grid<T>
+where
+    T: FromCell,
+{
+    type Err = GridParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, T::from_cell)
+    }
+}
+
+impl<T> Grid<T> {
+    /// A `width` x `height` grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// A `width` x `height` grid where every cell is computed from its position.
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+    where
+        F: FnMut((usize, usize)) -> T,
+    {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (col, row)))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses one row per line, turning every character into a cell with `cell`. Leading and
+    /// trailing blank lines are ignored, any other row must be as wide as the first one.
+    pub fn parse_with<F>(s: &str, mut cell: F) -> Result<Self, GridParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let lines: Vec<_> = s.trim_matches(['\n', '\r']).lines().collect();
+        let width = match lines.first() {
+            Some(first) if !first.is_empty() => first.chars().count(),
+            _ => return Err(GridParseError::Empty),
+        };
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (row, line) in lines.iter().enumerate() {
+            let found = line.chars().count();
+            if found != width {
+                return Err(GridParseError::RaggedRow {
+                    row,
+                    expected: width,
+                    found,
+                });
+            }
+
+            for (col, c) in line.chars().enumerate() {
+                let value = cell(c).ok_or(GridParseError::InvalidCell { col, row, cell: c })?;
+                cells.push(value);
+            }
+        }
+
+        Ok(Self {
+            width,
+            height: lines.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (col, row): (usize, usize)) -> bool {
+        col < self.width && row < self.height
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[self.flat_index(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        if !self.contains(pos) {
+            return None;
+        }
+        let i = self.flat_index(pos);
+        Some(&mut self.cells[i])
+    }
+
+    /// All rows from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(|row| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        (row < self.height).then(|| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    /// All columns from left to right, each one top to bottom.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(move |col| self.column_cells(col))
+    }
+
+    pub fn column(&self, col: usize) -> Option<impl Iterator<Item = &T> + '_> {
+        (col < self.width).then(|| self.column_cells(col))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| ((i % self.width, i / self.width), cell))
+    }
+
+    /// The first position (row by row) whose cell matches `predicate`, e.g. the start marker.
+    pub fn position<F>(&self, mut predicate: F) -> Option<(usize, usize)>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.cells()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// The cell `step` away from `pos`, if that is still inside the grid.
+    pub fn offset(&self, pos: (usize, usize), step: Vector) -> Option<(usize, usize)> {
+        (Point::from(pos) + step)
+            .cell()
+            .filter(|&cell| self.contains(cell))
+    }
+
+    /// Like [`Grid::offset`], but leaving the grid on one side re-enters it on the opposite one.
+    /// `None` only for a grid without cells.
+    pub fn wrapping_offset(&self, pos: (usize, usize), step: Vector) -> Option<(usize, usize)> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+        let p = Point::from(pos) + step;
+        Some((
+            p.x.rem_euclid(self.width as i64) as usize,
+            p.y.rem_euclid(self.height as i64) as usize,
+        ))
+    }
+
+    /// The cells at each of `offsets` from `pos` that are inside the grid, in the order of
+    /// `offsets`.
+    pub fn neighbors_with<'a>(
+        &'a self,
+        pos: (usize, usize),
+        offsets: &'a [Vector],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&step| self.offset(pos, step))
+    }
+
+    /// The up to 4 orthogonally adjacent cells, clockwise starting north.
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors_with(pos, &Vector::NEIGHBORS4)
+    }
+
+    /// The up to 8 surrounding cells, clockwise starting north.
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors_with(pos, &Vector::NEIGHBORS8)
+    }
+
+    /// The cells at each of `offsets` from `pos` on a torus, see [`Grid::wrapping_offset`].
+    pub fn wrapping_neighbors_with<'a>(
+        &'a self,
+        pos: (usize, usize),
+        offsets: &'a [Vector],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&step| self.wrapping_offset(pos, step))
+    }
+
+    /// The 4 orthogonally adjacent cells on a torus, clockwise starting north. Grids narrower or
+    /// lower than 3 cells yield some cells more than once.
+    pub fn wrapping_neighbors4(
+        &self,
+        pos: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.wrapping_neighbors_with(pos, &Vector::NEIGHBORS4)
+    }
+
+    /// The 8 surrounding cells on a torus, clockwise starting north. Grids narrower or lower than
+    /// 3 cells yield some cells more than once.
+    pub fn wrapping_neighbors8(
+        &self,
+        pos: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.wrapping_neighbors_with(pos, &Vector::NEIGHBORS8)
+    }
+
+    /// The cells from `from` (included) onwards in steps of `step`, until the ray leaves the
+    /// grid. Use `take(n)` to stop after `n` cells, e.g. to read a word.
+    ///
+    /// Panics if `step` is zero.
+    pub fn ray(
+        &self,
+        from: (usize, usize),
+        step: Vector,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        assert_ne!(step, Vector::ZERO, "a ray needs a non-zero step");
+        std::iter::successors(self.get(from).map(|_| from), move |&pos| {
+            self.offset(pos, step)
+        })
+    }
+
+    /// Every cell on the infinite line through `a` and `b`, in the direction from `a` to `b`. The
+    /// two points don't have to be inside the grid themselves.
+    ///
+    /// Panics if `a == b`.
+    pub fn line_through(&self, a: Point, b: Point) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let step = (b - a).reduced();
+        assert_ne!(
+            step,
+            Vector::ZERO,
+            "two distinct points are needed for a line"
+        );
+
+        // range of t for which a + t * step stays within 0..len along one axis
+        let axis_range = |start: i64, step: i64, len: usize| {
+            let (lo, hi) = (-start, len as i64 - 1 - start);
+            match step {
+                0 if lo <= 0 && 0 <= hi => (i64::MIN, i64::MAX),
+                0 => (1, 0),
+                1.. => (ceil_div(lo, step), floor_div(hi, step)),
+                _ => (ceil_div(hi, step), floor_div(lo, step)),
+            }
+        };
+        let (x_lo, x_hi) = axis_range(a.x, step.x, self.width);
+        let (y_lo, y_hi) = axis_range(a.y, step.y, self.height);
+
+        (x_lo.max(y_lo)..=x_hi.min(y_hi)).map(move |t| (a + step * t).cell().unwrap())
+    }
+
+    /// A grid of the same size with `f` applied to every cell.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn column_cells(&self, col: usize) -> impl Iterator<Item = &T> + '_ {
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    fn flat_index(&self, (col, row): (usize, usize)) -> usize {
+        row * self.width + col
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    /// Mirrors the grid along its main diagonal, rows become columns.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(col, row)| {
+            self[(row, col)].clone()
+        })
+    }
+
+    /// Rotates the grid a quarter turn clockwise.
+    pub fn rotate90(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(col, row)| {
+            self[(row, self.height - 1 - col)].clone()
+        })
+    }
+
+    pub fn rotate180(&self) -> Self {
+        Self::from_fn(self.width, self.height, |(col, row)| {
+            self[(self.width - 1 - col, self.height - 1 - row)].clone()
+        })
+    }
+
+    /// Rotates the grid a quarter turn counter-clockwise.
+    pub fn rotate270(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(col, row)| {
+            self[(self.width - 1 - row, col)].clone()
+        })
+    }
+
+    /// Mirrors the grid left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        Self::from_fn(self.width, self.height, |(col, row)| {
+            self[(self.width - 1 - col, row)].clone()
+        })
+    }
+
+    /// Mirrors the grid top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        Self::from_fn(self.width, self.height, |(col, row)| {
+            self[(col, self.height - 1 - row)].clone()
+        })
+    }
+
+    /// All 8 ways of rotating and mirroring the grid, starting with the grid itself and its
+    /// clockwise rotations.
+    pub fn orientations(&self) -> [Self; 8] {
+        let flipped = self.flip_horizontal();
+        [
+            self.clone(),
+            self.rotate90(),
+            self.rotate180(),
+            self.rotate270(),
+            flipped.rotate90(),
+            flipped.rotate180(),
+            flipped.rotate270(),
+            flipped,
+        ]
+    }
+
+    /// The `width` x `height` part of the grid starting at `top_left`, `None` if it doesn't fit.
+    pub fn crop(&self, top_left: (usize, usize), width: usize, height: usize) -> Option<Self> {
+        let (left, top) = top_left;
+        if left + width > self.width || top + height > self.height {
+            return None;
+        }
+        Some(Self::from_fn(width, height, |(col, row)| {
+            self[(left + col, top + row)].clone()
+        }))
+    }
+
+    /// Repeats the grid `across` times to the right and `down` times downwards.
+    pub fn tile(&self, across: usize, down: usize) -> Self {
+        Self::from_fn(self.width * across, self.height * down, |(col, row)| {
+            self[(col % self.width, row % self.height)].clone()
+        })
+    }
+}
+
+fn floor_div(a: i64, b: i64) -> i64 {
+    let q = a / b;
+    if a % b != 0 && (a < 0) != (b < 0) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+fn ceil_div(a: i64, b: i64) -> i64 {
+    -floor_div(-a, b)
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the {width}x{height} grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "\
+#.S
+.#.
+E..
+";
+
+    #[test]
+    fn test_parse() {
+        let grid: Grid<char> = MAP.parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.get((2, 0)), Some(&'S'));
+        assert_eq!(grid[(0, 2)], 'E');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.position(|&c| c == 'E'), Some((0, 2)));
+
+        let digits: Grid<u8> = "0123\n4567\r\n".parse().unwrap();
+        assert_eq!((digits.width(), digits.height()), (4, 2));
+        assert_eq!(digits[(3, 1)], 7);
+
+        // e.g. a map pasted right after a blank line
+        let grid: Grid<char> = "\n\r\n#.\n.#\n\n".parse().unwrap();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&['#', '.'][..], &['.', '#'][..]]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("".parse::<Grid<char>>(), Err(GridParseError::Empty));
+        assert_eq!("\n\n".parse::<Grid<char>>(), Err(GridParseError::Empty));
+        assert_eq!(
+            "...\n..\n...".parse::<Grid<char>>(),
+            Err(GridParseError::RaggedRow {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            "012\n3x5".parse::<Grid<u8>>(),
+            Err(GridParseError::InvalidCell {
+                col: 1,
+                row: 1,
+                cell: 'x'
+            })
+        );
+
+        let walls = Grid::parse_with("#.\n.#", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        });
+        assert_eq!(walls.unwrap().map(|&w| w as u8)[(1, 1)], 1);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let mut grid = Grid::from_fn(3, 2, |(col, row)| 10 * row + col);
+        *grid.get_mut((1, 1)).unwrap() = 99;
+        grid[(2, 0)] = 42;
+        assert!(grid.get_mut((0, 2)).is_none());
+
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&[0, 1, 42][..], &[10, 99, 12][..]]
+        );
+        assert_eq!(grid.row(1), Some(&[10, 99, 12][..]));
+        assert_eq!(grid.row(2), None);
+
+        let columns: Vec<Vec<_>> = grid.columns().map(|c| c.copied().collect()).collect();
+        assert_eq!(columns, vec![vec![0, 10], vec![1, 99], vec![42, 12]]);
+        assert_eq!(grid.column(1).unwrap().sum::<usize>(), 100);
+        assert!(grid.column(3).is_none());
+
+        assert_eq!(grid.cells().nth(4), Some(((1, 1), &99)));
+        assert_eq!(Grid::new(2, 2, '.').cells().count(), 4);
+        // grids without cells still have their rows and columns, just empty ones
+        let flat = Grid::new(3, 0, '.');
+        assert_eq!(flat.rows().count(), 0);
+        assert_eq!(
+            flat.columns().map(|c| c.count()).collect::<Vec<_>>(),
+            vec![0; 3]
+        );
+        assert_eq!(flat.column(1).unwrap().count(), 0);
+        let thin = Grid::new(0, 3, '.');
+        assert_eq!(thin.rows().collect::<Vec<_>>(), vec![&[][..]; 3]);
+        assert_eq!(thin.row(1), Some(&[][..]));
+        assert_eq!(thin.columns().count(), 0);
+        assert_eq!(
+            thin.transpose().columns().map(|c| c.count()).sum::<usize>(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(4, 3, ());
+
+        let corner: Vec<_> = grid.neighbors4((0, 0)).collect();
+        assert_eq!(corner, vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(
+            grid.neighbors8((3, 2)).collect::<Vec<_>>(),
+            vec![(3, 1), (2, 2), (2, 1)]
+        );
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+
+        let knight = [Vector::new(1, 2), Vector::new(2, -1), Vector::new(-1, 2)];
+        assert_eq!(
+            grid.neighbors_with((0, 0), &knight).collect::<Vec<_>>(),
+            vec![(1, 2)]
+        );
+        assert_eq!(grid.offset((3, 2), Vector::new(1, 0)), None);
+    }
+
+    #[test]
+    fn test_wrapping_neighbors() {
+        let grid = Grid::new(4, 3, ());
+
+        assert_eq!(
+            grid.wrapping_neighbors4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 2), (1, 0), (0, 1), (3, 0)]
+        );
+        assert_eq!(
+            grid.wrapping_neighbors8((3, 2)).collect::<Vec<_>>(),
+            vec![
+                (3, 1),
+                (0, 1),
+                (0, 2),
+                (0, 0),
+                (3, 0),
+                (2, 0),
+                (2, 2),
+                (2, 1)
+            ]
+        );
+        assert_eq!(
+            grid.wrapping_offset((3, 2), Vector::new(9, -7)),
+            Some((0, 1))
+        );
+
+        // nowhere to wrap around to
+        for empty in [Grid::new(0, 3, ()), Grid::new(4, 0, ())] {
+            assert_eq!(empty.wrapping_offset((0, 0), Vector::new(1, 0)), None);
+            assert_eq!(empty.wrapping_neighbors8((0, 0)).count(), 0);
+        }
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid: Grid<char> = "XMAS\n.M..\n..A.\n...S".parse().unwrap();
+        let word = |from, step| {
+            grid.ray(from, step)
+                .take(4)
+                .map(|pos| grid[pos])
+                .collect::<String>()
+        };
+
+        assert_eq!(word((0, 0), Vector::new(1, 0)), "XMAS");
+        assert_eq!(word((0, 0), Vector::new(1, 1)), "XMAS");
+        assert_eq!(word((3, 3), Vector::new(-1, -1)), "SAMX");
+        assert_eq!(word((2, 1), Vector::new(1, 0)), "..");
+        assert_eq!(grid.ray((0, 3), Vector::new(0, -1)).count(), 4);
+        assert_eq!(grid.ray((4, 0), Vector::new(1, 0)).count(), 0);
+    }
+
+    #[test]
+    fn test_line_through() {
+        let grid = Grid::new(10, 10, ());
+
+        // the antennas from 2024 day 8, every cell in line with them
+        let line: Vec<_> = grid
+            .line_through(Point::new(4, 3), Point::new(5, 5))
+            .collect();
+        assert_eq!(line, vec![(3, 1), (4, 3), (5, 5), (6, 7), (7, 9)]);
+
+        let reversed: Vec<_> = grid
+            .line_through(Point::new(8, 11), Point::new(6, 7))
+            .collect();
+        assert_eq!(reversed, line.into_iter().rev().collect::<Vec<_>>());
+
+        assert_eq!(
+            grid.line_through(Point::new(-3, 2), Point::new(-1, 2))
+                .count(),
+            10
+        );
+        assert_eq!(
+            grid.line_through(Point::new(-3, 2), Point::new(-3, 4))
+                .count(),
+            0
+        );
+        assert_eq!(
+            grid.line_through(Point::new(0, 0), Point::new(6, 4))
+                .collect::<Vec<_>>(),
+            vec![(0, 0), (3, 2), (6, 4), (9, 6)]
+        );
+    }
+
+    fn text(grid: &Grid<char>) -> String {
+        grid.rows()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_transformations() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+
+        assert_eq!(text(&grid.transpose()), "ad\nbe\ncf");
+        assert_eq!(text(&grid.rotate90()), "da\neb\nfc");
+        assert_eq!(text(&grid.rotate180()), "fed\ncba");
+        assert_eq!(text(&grid.rotate270()), "cf\nbe\nad");
+        assert_eq!(text(&grid.flip_horizontal()), "cba\nfed");
+        assert_eq!(text(&grid.flip_vertical()), "def\nabc");
+
+        assert_eq!(grid.rotate90().rotate270(), grid);
+        assert_eq!(grid.rotate90().rotate90(), grid.rotate180());
+        assert_eq!(grid.transpose().transpose(), grid);
+
+        let orientations = grid.orientations();
+        assert_eq!(orientations[0], grid);
+        for (i, a) in orientations.iter().enumerate() {
+            assert!(orientations[i + 1..].iter().all(|b| a != b));
+        }
+        assert!(orientations.contains(&grid.transpose()));
+        assert!(orientations.contains(&grid.flip_vertical()));
+    }
+
+    #[test]
+    fn test_crop_and_tile() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+
+        assert_eq!(text(&grid.crop((1, 0), 2, 2).unwrap()), "bc\nef");
+        assert_eq!(grid.crop((2, 1), 1, 1).map(|g| g[(0, 0)]), Some('f'));
+        assert_eq!(grid.crop((2, 0), 2, 1), None);
+        assert_eq!(grid.crop((0, 0), 3, 2), Some(grid.clone()));
+
+        assert_eq!(text(&grid.tile(2, 2)), "abcabc\ndefdef\nabcabc\ndefdef");
+        assert_eq!(grid.tile(1, 1), grid);
+    }
+
+    #[test]
+    fn test_word_search_orientations() {
+        // horizontal and vertical XMAS in both directions, found by scanning rows of rotations
+        let grid: Grid<char> = "XMASX\n....M\nSAMXA\n....S".parse().unwrap();
+        let count: usize = [
+            grid.clone(),
+            grid.rotate90(),
+            grid.rotate180(),
+            grid.rotate270(),
+        ]
+        .iter()
+        .map(|g| text(g).matches("XMAS").count())
+        .sum();
+        assert_eq!(count, 3);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::search::Successors;
+use crate::{Direction, Graph, Grid, IdTrait, PlainNode, Vector};
+
+/// Exposes a [`Grid`] as a graph: every passable cell is a node, with edges to the passable
+/// cells around it (the 4 orthogonal ones unless [`GridGraph::with_offsets`] says otherwise).
+///
+/// `passable` decides which cells can be entered, and `cost(from, to)` prices a single step.
+/// Steps off the grid are never generated, so maps don't need a surrounding wall.
+///
+/// Use it either as [`Successors`] (through a reference) or convert it with
+/// [`GridGraph::to_graph`].
+pub struct GridGraph<'a, T, P, C> {
+    grid: &'a Grid<T>,
+    passable: P,
+    cost: C,
+    offsets: &'a [Vector],
+}
+
+/// A cell together with the direction you are facing, the nodes of [`HeadingGridGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pose {
+    pub pos: (usize, usize),
+    pub dir: Direction,
+}
+
+impl IdTrait for Pose {}
+
+impl<'a, T, P, C> GridGraph<'a, T, P, C>
+where
+    P: Fn(&T) -> bool,
+    C: Fn((usize, usize), (usize, usize)) -> usize,
+{
+    pub fn new(grid: &'a Grid<T>, passable: P, cost: C) -> Self {
+        Self {
+            grid,
+            passable,
+            cost,
+            offsets: &Vector::NEIGHBORS4,
+        }
+    }
+
+    /// Connects each cell to the ones at `offsets` instead, e.g. [`Vector::NEIGHBORS8`].
+    pub fn with_offsets(self, offsets: &'a [Vector]) -> Self {
+        Self { offsets, ..self }
+    }
+
+    /// Switches to [`Pose`] nodes: a step goes one cell forward in the direction faced, and
+    /// turning 90 degrees on the spot costs `turn_cost`. Offsets are ignored.
+    pub fn with_heading(self, turn_cost: usize) -> HeadingGridGraph<'a, T, P, C> {
+        HeadingGridGraph {
+            inner: self,
+            turn_cost,
+        }
+    }
+
+    pub fn grid(&self) -> &'a Grid<T> {
+        self.grid
+    }
+
+    pub fn is_passable(&self, pos: (usize, usize)) -> bool {
+        self.grid.get(pos).is_some_and(&self.passable)
+    }
+
+    /// Every passable cell, row by row.
+    pub fn nodes(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.grid
+            .cells()
+            .filter(|(_, cell)| (self.passable)(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// The steps out of `pos` as `(target, cost)` pairs.
+    pub fn edges(&self, pos: (usize, usize)) -> impl Iterator<Item = ((usize, usize), usize)> + '_ {
+        self.grid
+            .neighbors_with(pos, self.offsets)
+            .filter(|&next| self.is_passable(next))
+            .map(move |next| (next, (self.cost)(pos, next)))
+    }
+
+    /// Builds the explicit graph with `(col, row)` node ids.
+    pub fn to_graph(&self) -> Graph<PlainNode<(usize, usize)>> {
+        let edges = self
+            .nodes()
+            .flat_map(|pos| self.edges(pos).map(move |(next, cost)| (pos, next, cost)));
+        Graph::from_edges(self.nodes(), edges)
+    }
+}
+
+impl<T, P, C> Successors<(usize, usize)> for &GridGraph<'_, T, P, C>
+where
+    P: Fn(&T) -> bool,
+    C: Fn((usize, usize), (usize, usize)) -> usize,
+{
+    fn for_each_successor<F>(&mut self, node: &(usize, usize), mut f: F)
+    where
+        F: FnMut((usize, usize), usize),
+    {
+        for (next, cost) in self.edges(*node) {
+            f(next, cost);
+        }
+    }
+}
+
+/// A [`GridGraph`] whose nodes also remember which way they face, see
+/// [`GridGraph::with_heading`]. This is the usual reindeer maze state.
+pub struct HeadingGridGraph<'a, T, P, C> {
+    inner: GridGraph<'a, T, P, C>,
+    turn_cost: usize,
+}
+
+impl<T, P, C> HeadingGridGraph<'_, T, P, C>
+where
+    P: Fn(&T) -> bool,
+    C: Fn((usize, usize), (usize, usize)) -> usize,
+{
+    /// Every passable cell in each of the four directions.
+    pub fn nodes(&self) -> impl Iterator<Item = Pose> + '_ {
+        self.inner
+            .nodes()
+            .flat_map(|pos| Direction::ALL.map(|dir| Pose { pos, dir }))
+    }
+
+    /// The moves out of `pose` as `(target, cost)` pairs: turning left, turning right and, if
+    /// possible, stepping forward.
+    pub fn edges(&self, pose: Pose) -> impl Iterator<Item = (Pose, usize)> + '_ {
+        let turns = [pose.dir.turn_left(), pose.dir.turn_right()].map(|dir| {
+            let turned = Pose { dir, ..pose };
+            (turned, self.turn_cost)
+        });
+        let forward = self
+            .inner
+            .grid
+            .offset(pose.pos, pose.dir.vector())
+            .filter(|&next| self.inner.is_passable(next))
+            .map(|next| {
+                (
+                    Pose { pos: next, ..pose },
+                    (self.inner.cost)(pose.pos, next),
+                )
+            });
+
+        forward.into_iter().chain(turns)
+    }
+
+    /// Builds the explicit graph with [`Pose`] node ids.
+    pub fn to_graph(&self) -> Graph<PlainNode<Pose>> {
+        let edges = self
+            .nodes()
+            .flat_map(|pose| self.edges(pose).map(move |(next, cost)| (pose, next, cost)));
+        Graph::from_edges(self.nodes(), edges)
+    }
+}
+
+impl<T, P, C> Successors<Pose> for &HeadingGridGraph<'_, T, P, C>
+where
+    P: Fn(&T) -> bool,
+    C: Fn((usize, usize), (usize, usize)) -> usize,
+{
+    fn for_each_successor<F>(&mut self, node: &Pose, mut f: F)
+    where
+        F: FnMut(Pose, usize),
+    {
+        for (next, cost) in self.edges(*node) {
+            f(next, cost);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::{bfs, dijkstra_iter};
+
+    // the first example from 2024 day 16
+    const MAZE: &str = "\
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############";
+
+    #[test]
+    fn test_grid_graph() {
+        // no surrounding wall, and the cost of a step is the height of the target cell
+        let grid: Grid<u8> = "129\n345\n999".parse().unwrap();
+        let adapter = GridGraph::new(&grid, |&h| h < 9, |_, to| grid[to] as usize);
+
+        assert_eq!(adapter.nodes().count(), 5);
+        assert_eq!(
+            adapter.edges((0, 0)).collect::<Vec<_>>(),
+            vec![((1, 0), 2), ((0, 1), 3)]
+        );
+
+        let graph = adapter.to_graph();
+        let result = graph.dijkstra(&(0, 0), None);
+        assert_eq!(result.dist[&(2, 1)], 2 + 4 + 5);
+        assert_eq!(result.dist.len(), 5);
+
+        let diagonal =
+            GridGraph::new(&grid, |&h| h < 9, |_, _| 1).with_offsets(&Vector::NEIGHBORS8);
+        assert_eq!(bfs((0, 0), None, &diagonal).dist[&(2, 1)], 2);
+    }
+
+    #[test]
+    fn test_heading_grid_graph() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let start = grid.position(|&c| c == 'S').unwrap();
+        let end = grid.position(|&c| c == 'E').unwrap();
+        let reindeer = GridGraph::new(&grid, |&c| c != '#', |_, _| 1).with_heading(1000);
+
+        let start = Pose {
+            pos: start,
+            dir: Direction::East,
+        };
+        let (_, score) = dijkstra_iter(start, &reindeer)
+            .find(|(pose, _)| pose.pos == end)
+            .unwrap();
+        assert_eq!(score, 7036);
+
+        let graph = reindeer.to_graph();
+        let result = graph.dijkstra(&start, None);
+        let best = Direction::ALL
+            .map(|dir| result.dist[&Pose { pos: end, dir }])
+            .into_iter()
+            .min();
+        assert_eq!(best, Some(7036));
+    }
+}
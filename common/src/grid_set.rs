@@ -0,0 +1,306 @@
+use crate::Grid;
+
+/// A set of grid positions, stored as one bit per cell. Meant as a drop-in for
+/// `HashSet<(usize, usize)>` in simulations that insert and look up positions all the time.
+///
+/// Set operations require both sets to have the same dimensions.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GridBitSet {
+    width: usize,
+    height: usize,
+    words: Vec<u64>,
+}
+
+impl GridBitSet {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            words: vec![0; (width * height).div_ceil(64)],
+        }
+    }
+
+    /// An empty set covering the same area as `grid`.
+    pub fn for_grid<T>(grid: &Grid<T>) -> Self {
+        Self::new(grid.width(), grid.height())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// `false` for positions outside the grid.
+    pub fn contains(&self, pos: (usize, usize)) -> bool {
+        self.bit(pos)
+            .is_some_and(|i| self.words[i / 64] & (1 << (i % 64)) != 0)
+    }
+
+    /// Adds `pos`, returning whether it was new. Panics if `pos` is outside the grid.
+    pub fn insert(&mut self, pos: (usize, usize)) -> bool {
+        let i = self.bit_or_panic(pos);
+        let was_set = self.words[i / 64] & (1 << (i % 64)) != 0;
+        self.words[i / 64] |= 1 << (i % 64);
+        !was_set
+    }
+
+    /// Removes `pos`, returning whether it was there.
+    pub fn remove(&mut self, pos: (usize, usize)) -> bool {
+        let Some(i) = self.bit(pos) else {
+            return false;
+        };
+        let was_set = self.words[i / 64] & (1 << (i % 64)) != 0;
+        self.words[i / 64] &= !(1 << (i % 64));
+        was_set
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub fn union_with(&mut self, other: &GridBitSet) {
+        self.combine(other, |a, b| a | b);
+    }
+
+    pub fn intersect_with(&mut self, other: &GridBitSet) {
+        self.combine(other, |a, b| a & b);
+    }
+
+    pub fn difference_with(&mut self, other: &GridBitSet) {
+        self.combine(other, |a, b| a & !b);
+    }
+
+    /// Every position in the set, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.words.iter().enumerate().flat_map(move |(w, &word)| {
+            let mut bits = word;
+            std::iter::from_fn(move || {
+                if bits == 0 {
+                    return None;
+                }
+                let i = w * 64 + bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                Some((i % self.width, i / self.width))
+            })
+        })
+    }
+
+    fn bit(&self, (col, row): (usize, usize)) -> Option<usize> {
+        (col < self.width && row < self.height).then_some(row * self.width + col)
+    }
+
+    fn bit_or_panic(&self, pos: (usize, usize)) -> usize {
+        self.bit(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the {}x{} grid", self.width, self.height))
+    }
+
+    fn combine<F>(&mut self, other: &GridBitSet, f: F)
+    where
+        F: Fn(u64, u64) -> u64,
+    {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "grid sets must have the same dimensions"
+        );
+        for (a, &b) in self.words.iter_mut().zip(&other.words) {
+            *a = f(*a, b);
+        }
+    }
+}
+
+impl Extend<(usize, usize)> for GridBitSet {
+    fn extend<I>(&mut self, positions: I)
+    where
+        I: IntoIterator<Item = (usize, usize)>,
+    {
+        for pos in positions {
+            self.insert(pos);
+        }
+    }
+}
+
+/// A map from grid positions to values, with a [`GridBitSet`] tracking which cells are set so
+/// that `len`, `clear` and iteration only touch occupied cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridMap<V> {
+    keys: GridBitSet,
+    values: Vec<Option<V>>,
+}
+
+impl<V> GridMap<V> {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            keys: GridBitSet::new(width, height),
+            values: (0..width * height).map(|_| None).collect(),
+        }
+    }
+
+    /// An empty map covering the same area as `grid`.
+    pub fn for_grid<T>(grid: &Grid<T>) -> Self {
+        Self::new(grid.width(), grid.height())
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The occupied positions.
+    pub fn keys(&self) -> &GridBitSet {
+        &self.keys
+    }
+
+    pub fn contains_key(&self, pos: (usize, usize)) -> bool {
+        self.keys.contains(pos)
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&V> {
+        self.keys.bit(pos).and_then(|i| self.values[i].as_ref())
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut V> {
+        self.keys.bit(pos).and_then(|i| self.values[i].as_mut())
+    }
+
+    /// Sets the value at `pos`, returning the previous one. Panics if `pos` is outside the grid.
+    pub fn insert(&mut self, pos: (usize, usize), value: V) -> Option<V> {
+        let i = self.keys.bit_or_panic(pos);
+        self.keys.insert(pos);
+        self.values[i].replace(value)
+    }
+
+    /// The value at `pos`, inserting `default()` first if there is none.
+    pub fn get_or_insert_with<F>(&mut self, pos: (usize, usize), default: F) -> &mut V
+    where
+        F: FnOnce() -> V,
+    {
+        let i = self.keys.bit_or_panic(pos);
+        self.keys.insert(pos);
+        self.values[i].get_or_insert_with(default)
+    }
+
+    pub fn remove(&mut self, pos: (usize, usize)) -> Option<V> {
+        let i = self.keys.bit(pos)?;
+        self.keys.remove(pos);
+        self.values[i].take()
+    }
+
+    /// Empties the map, only visiting the occupied cells.
+    pub fn clear(&mut self) {
+        for (col, row) in self.keys.iter() {
+            self.values[row * self.keys.width + col] = None;
+        }
+        self.keys.clear();
+    }
+
+    /// Every entry, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &V)> + '_ {
+        self.keys.iter().map(|(col, row)| {
+            let value = self.values[row * self.keys.width + col].as_ref().unwrap();
+            ((col, row), value)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitset() {
+        // 130 cells, so the set spans three words
+        let mut set = GridBitSet::new(13, 10);
+        assert!(set.is_empty());
+        assert!(set.insert((0, 0)));
+        assert!(set.insert((12, 9)));
+        assert!(set.insert((11, 4)));
+        assert!(!set.insert((11, 4)));
+        assert_eq!(set.len(), 3);
+
+        assert!(set.contains((12, 9)));
+        assert!(!set.contains((13, 0)));
+        assert!(!set.contains((0, 1)));
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![(0, 0), (11, 4), (12, 9)]
+        );
+
+        assert!(set.remove((0, 0)));
+        assert!(!set.remove((0, 0)));
+        assert!(!set.remove((20, 20)));
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_bitset_operations() {
+        let mut a = GridBitSet::new(4, 4);
+        a.extend([(0, 0), (1, 1), (2, 2)]);
+        let mut b = GridBitSet::new(4, 4);
+        b.extend([(2, 2), (3, 3)]);
+
+        let mut union = a.clone();
+        union.union_with(&b);
+        assert_eq!(union.len(), 4);
+
+        let mut both = a.clone();
+        both.intersect_with(&b);
+        assert_eq!(both.iter().collect::<Vec<_>>(), vec![(2, 2)]);
+
+        a.difference_with(&b);
+        assert_eq!(a.iter().collect::<Vec<_>>(), vec![(0, 0), (1, 1)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bitset_dimensions() {
+        let mut a = GridBitSet::new(4, 4);
+        a.union_with(&GridBitSet::new(2, 8));
+    }
+
+    #[test]
+    fn test_grid_map() {
+        let grid = Grid::new(5, 3, '.');
+        let mut visited: GridMap<Vec<char>> = GridMap::for_grid(&grid);
+
+        visited.get_or_insert_with((1, 2), Vec::new).push('^');
+        visited.get_or_insert_with((1, 2), Vec::new).push('>');
+        assert_eq!(visited.insert((4, 0), vec!['v']), None);
+        assert_eq!(visited.insert((4, 0), vec!['<']), Some(vec!['v']));
+
+        assert_eq!(visited.len(), 2);
+        assert_eq!(visited.get((1, 2)), Some(&vec!['^', '>']));
+        assert_eq!(visited.get((2, 2)), None);
+        assert!(visited.contains_key((4, 0)));
+        assert_eq!(
+            visited
+                .iter()
+                .map(|(pos, v)| (pos, v.len()))
+                .collect::<Vec<_>>(),
+            vec![((4, 0), 1), ((1, 2), 2)]
+        );
+
+        visited.get_mut((4, 0)).unwrap().push('^');
+        assert_eq!(visited.remove((4, 0)), Some(vec!['<', '^']));
+        assert_eq!(visited.keys().len(), 1);
+
+        visited.clear();
+        assert!(visited.is_empty());
+        assert_eq!(visited.get((1, 2)), None);
+    }
+}
@@ -0,0 +1,105 @@
+use std::io::{self, Write};
+
+use crate::{Grid, Point};
+
+/// A colour as red, green and blue intensities.
+pub type Rgb = [u8; 3];
+
+/// Writes `grid` as a binary PBM (`P4`) bitmap, one pixel per cell. Cells for which `is_black`
+/// holds become black pixels.
+pub fn write_pbm<T, W, F>(out: &mut W, grid: &Grid<T>, is_black: F) -> io::Result<()>
+where
+    W: Write + ?Sized,
+    F: Fn(&T) -> bool,
+{
+    write!(out, "P4\n{} {}\n", grid.width(), grid.height())?;
+
+    // every row is packed into whole bytes, most significant bit first
+    for row in grid.rows() {
+        let bytes: Vec<u8> = row
+            .chunks(8)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, cell)| is_black(cell))
+                    .fold(0, |byte, (i, _)| byte | (0x80 >> i))
+            })
+            .collect();
+        out.write_all(&bytes)?;
+    }
+    Ok(())
+}
+
+/// Writes `grid` as a binary PPM (`P6`) image, one pixel per cell coloured by `palette`.
+pub fn write_ppm<T, W, F>(out: &mut W, grid: &Grid<T>, palette: F) -> io::Result<()>
+where
+    W: Write + ?Sized,
+    F: Fn(&T) -> Rgb,
+{
+    write!(out, "P6\n{} {}\n255\n", grid.width(), grid.height())?;
+    let pixels: Vec<u8> = grid.rows().flatten().flat_map(palette).collect();
+    out.write_all(&pixels)
+}
+
+/// Counts how many of `points` land on every cell of a `width` x `height` grid, e.g. robots
+/// that share a tile. Points outside the grid are dropped.
+pub fn point_cloud<I>(width: usize, height: usize, points: I) -> Grid<usize>
+where
+    I: IntoIterator<Item = Point>,
+{
+    let mut counts = Grid::new(width, height, 0);
+    for cell in points.into_iter().filter_map(Point::cell) {
+        if let Some(count) = counts.get_mut(cell) {
+            *count += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pbm() {
+        let grid: Grid<char> = "#........#\n.#########".parse().unwrap();
+        let mut out = vec![];
+        write_pbm(&mut out, &grid, |&c| c == '#').unwrap();
+
+        let mut expected = b"P4\n10 2\n".to_vec();
+        expected.extend([0b1000_0000, 0b0100_0000, 0b0111_1111, 0b1100_0000]);
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_ppm_point_cloud() {
+        let robots = [
+            Point::new(0, 0),
+            Point::new(2, 1),
+            Point::new(2, 1),
+            Point::new(-1, 0),
+            Point::new(3, 0),
+        ];
+        let cloud = point_cloud(3, 2, robots);
+        assert_eq!(
+            cloud.rows().collect::<Vec<_>>(),
+            vec![&[1, 0, 0], &[0, 0, 2]]
+        );
+
+        let mut out = vec![];
+        write_ppm(&mut out, &cloud, |&n| match n {
+            0 => [0, 0, 0],
+            1 => [0, 255, 0],
+            _ => [255, 0, 0],
+        })
+        .unwrap();
+
+        let header = b"P6\n3 2\n255\n";
+        assert_eq!(&out[..header.len()], header);
+        let pixels = &out[header.len()..];
+        assert_eq!(pixels.len(), 3 * 6);
+        assert_eq!(&pixels[..3], &[0, 255, 0]);
+        assert_eq!(&pixels[15..], &[255, 0, 0]);
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{BinaryHeapQueue, DijkstraIter, Graph, IdTrait, Node, PriorityQueue};
+
+/// Compact copy of a [`Graph`] where nodes are numbered `0..n` in ascending id order, for
+/// algorithms that want plain vectors instead of hash maps.
+#[derive(Debug, Clone)]
+pub(crate) struct IndexedGraph<N>
+where
+    N: IdTrait,
+{
+    pub(crate) ids: Vec<N>,
+    pub(crate) index: HashMap<N, usize>,
+    /// outgoing `(target, cost)` pairs, in the order they are stored in the graph
+    pub(crate) adj: Vec<Vec<(usize, usize)>>,
+}
+
+impl<N> IndexedGraph<N>
+where
+    N: IdTrait,
+{
+    pub(crate) fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Shortest distances from `source` to every node, `usize::MAX` for unreachable ones.
+    pub(crate) fn distances_from(&self, source: usize) -> Vec<usize> {
+        self.distances_using(source, BinaryHeapQueue::default())
+    }
+
+    /// Like [`IndexedGraph::distances_from`], with the search running on `queue`.
+    pub(crate) fn distances_using<Q>(&self, source: usize, queue: Q) -> Vec<usize>
+    where
+        Q: PriorityQueue<usize>,
+    {
+        let successors = |&u: &usize| self.adj[u].iter().copied();
+        let search = DijkstraIter::with_queue(source, successors, (), queue);
+
+        let mut dist = vec![usize::MAX; self.len()];
+        for (node, d) in search {
+            dist[node] = d;
+        }
+        dist
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Node,
+{
+    pub(crate) fn indexed(&self) -> IndexedGraph<T::NodeIdType> {
+        let ids: BTreeSet<_> = self
+            .nodes
+            .keys()
+            .chain(self.adjacency_list.keys())
+            .chain(self.adjacency_list.values().flatten().map(|e| &e.node))
+            .cloned()
+            .collect();
+        let ids: Vec<_> = ids.into_iter().collect();
+        let index: HashMap<_, _> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (id.clone(), i))
+            .collect();
+
+        let mut adj = vec![vec![]; ids.len()];
+        for (from, edges) in &self.adjacency_list {
+            adj[index[from]] = edges.iter().map(|e| (index[&e.node], e.cost)).collect();
+        }
+
+        IndexedGraph { ids, index, adj }
+    }
+}
@@ -1,10 +1,54 @@
-use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fs::File;
 use std::hash::Hash;
 use std::io::{self, BufRead};
 use std::path::Path;
+
+pub mod cast;
+pub mod cliques;
+pub mod connectivity;
+pub mod dag;
+pub mod euler;
+pub mod generators;
+pub mod geometry;
+pub mod grid;
+pub mod grid_adapter;
+pub mod grid_set;
+pub mod image;
+mod indexed;
+pub mod matching;
+pub mod metrics;
+pub mod parallel;
+pub mod queue;
+pub mod regions;
+pub mod render;
+pub mod search;
+#[cfg(test)]
+mod test_graphs;
+pub mod tsp;
+pub mod undirected;
+
+pub use cast::CastRecorder;
+pub use connectivity::Dominators;
+pub use dag::{CycleError, Reachability, WeightedPath};
+pub use euler::EulerError;
+pub use generators::{SplitMix64, braided_maze, erdos_renyi, grid_graph, perfect_maze};
+pub use geometry::{Direction, Point, Vector};
+pub use grid::{FromCell, Grid, GridParseError};
+pub use grid_adapter::{GridGraph, HeadingGridGraph, Pose};
+pub use grid_set::{GridBitSet, GridMap};
+pub use image::{Rgb, point_cloud, write_pbm, write_ppm};
+pub use matching::{Bipartition, NotBipartite};
+pub use queue::{BinaryHeapQueue, BucketQueue, PriorityQueue, RadixHeap, ZeroOneQueue};
+pub use regions::{Region, Regions};
+pub use render::{Color, Layer, Renderer};
+pub use search::{
+    BfsIter, DijkstraIter, GraphSuccessors, Successors, bfs, bfs_iter, dijkstra_iter, zero_one_bfs,
+};
+pub use tsp::{MAX_TSP_POINTS, Tour, TourKind, TspError, held_karp};
+pub use undirected::UndirectedView;
+
 // The output is wrapped in a Result to allow matching on errors
 // Returns an Iterator to the Reader of the lines of the file.
 pub fn read_lines<P>(filename: P) -> io::Result<impl Iterator<Item = String>>
@@ -24,6 +68,26 @@ pub trait Node: Debug + Clone {
     fn node_id(&self) -> Self::NodeIdType;
 }
 
+impl IdTrait for usize {}
+impl IdTrait for (usize, usize) {}
+
+/// A node that carries nothing but its id, for graphs where the id says it all.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct PlainNode<N>(pub N)
+where
+    N: IdTrait;
+
+impl<N> Node for PlainNode<N>
+where
+    N: IdTrait,
+{
+    type NodeIdType = N;
+
+    fn node_id(&self) -> Self::NodeIdType {
+        self.0.clone()
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct Edge<T>
 where
@@ -66,6 +130,69 @@ where
     pub prev: HashMap<NodeId, Option<NodeId>>,
 }
 
+impl<N> Graph<PlainNode<N>>
+where
+    N: IdTrait,
+{
+    /// Builds a graph from `(from, to, cost)` triples. Nodes only need to be listed in `nodes` if
+    /// they have no edges at all.
+    pub fn from_edges(
+        nodes: impl IntoIterator<Item = N>,
+        edges: impl IntoIterator<Item = (N, N, usize)>,
+    ) -> Self {
+        let mut adjacency_list: <Self as Graphable>::AdjacencyList = HashMap::new();
+        for node in nodes {
+            adjacency_list.entry(node).or_default();
+        }
+        for (from, to, cost) in edges {
+            adjacency_list.entry(to.clone()).or_default();
+            adjacency_list
+                .entry(from)
+                .or_default()
+                .push(Edge { node: to, cost });
+        }
+
+        let nodes = adjacency_list
+            .keys()
+            .map(|n| (n.clone(), PlainNode(n.clone())))
+            .collect();
+        Self::new(nodes, adjacency_list)
+    }
+}
+
+/// Callbacks invoked by [`Graph::dijkstra_with`] as the search progresses.
+///
+/// All methods default to doing nothing, so implementors only override what they need.
+pub trait DijkstraVisitor<NodeId> {
+    /// `node` was reached for the first time, with a tentative distance of `dist`.
+    fn node_discovered(&mut self, _node: &NodeId, _dist: usize) {}
+
+    /// the edge `from -> to` improved the tentative distance of `to` to `dist`.
+    fn edge_relaxed(&mut self, _from: &NodeId, _to: &NodeId, _dist: usize) {}
+
+    /// `node` was popped from the queue, `dist` is its final distance.
+    fn node_settled(&mut self, _node: &NodeId, _dist: usize) {}
+}
+
+impl<NodeId> DijkstraVisitor<NodeId> for () {}
+
+impl<NodeId, V> DijkstraVisitor<NodeId> for &mut V
+where
+    V: DijkstraVisitor<NodeId> + ?Sized,
+{
+    fn node_discovered(&mut self, node: &NodeId, dist: usize) {
+        (**self).node_discovered(node, dist);
+    }
+
+    fn edge_relaxed(&mut self, from: &NodeId, to: &NodeId, dist: usize) {
+        (**self).edge_relaxed(from, to, dist);
+    }
+
+    fn node_settled(&mut self, node: &NodeId, dist: usize) {
+        (**self).node_settled(node, dist);
+    }
+}
+
 impl<T> Graph<T>
 where
     T: Node,
@@ -85,67 +212,78 @@ where
         start: &T::NodeIdType,
         end: Option<&T::NodeIdType>,
     ) -> Dijkstra<T::NodeIdType> {
-        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-        struct QueueEntry<N>
-        where
-            N: IdTrait,
-        {
-            distance: Reverse<usize>,
-            node_id: N,
-        }
-
-        let mut d_res: Dijkstra<T::NodeIdType> = Dijkstra {
-            dist: HashMap::new(),
-            prev: HashMap::new(),
-        };
-
-        let mut q = BinaryHeap::<QueueEntry<T::NodeIdType>>::new();
-
-        // initialize the internal data structures
-        for v in self.nodes.keys() {
-            d_res.prev.insert(v.clone(), None);
-            d_res.dist.insert(v.clone(), usize::MAX);
-        }
+        self.dijkstra_with(start, &mut (), |node_id, _| {
+            end.is_some_and(|end_node| end_node == node_id)
+        })
+    }
 
-        d_res.dist.insert(start.clone(), 0);
-        q.push(QueueEntry {
-            node_id: start.clone(),
-            distance: Reverse(0),
-        });
+    /// Runs dijkstra from `start`, reporting progress to `visitor`.
+    ///
+    /// `stop` is evaluated for every settled node (after the visitor has seen it), and returning
+    /// `true` ends the search before that node's edges are relaxed.
+    pub fn dijkstra_with<V, S>(
+        &self,
+        start: &T::NodeIdType,
+        visitor: &mut V,
+        stop: S,
+    ) -> Dijkstra<T::NodeIdType>
+    where
+        V: DijkstraVisitor<T::NodeIdType>,
+        S: FnMut(&T::NodeIdType, usize) -> bool,
+    {
+        let successors = GraphSuccessors::new(self);
+        let search = DijkstraIter::with_visitor(start.clone(), successors, visitor);
+        self.run_dijkstra(search, stop)
+    }
 
-        while let Some(QueueEntry { distance, node_id }) = q.pop() {
-            let u_node_id = node_id;
+    /// Like [`Graph::dijkstra`], but with a different priority queue, e.g. a
+    /// [`BucketQueue`] when all edge costs are small.
+    pub fn dijkstra_using<Q>(
+        &self,
+        start: &T::NodeIdType,
+        end: Option<&T::NodeIdType>,
+        queue: Q,
+    ) -> Dijkstra<T::NodeIdType>
+    where
+        Q: PriorityQueue<T::NodeIdType>,
+    {
+        let successors = GraphSuccessors::new(self);
+        let search = DijkstraIter::with_queue(start.clone(), successors, (), queue);
+        self.run_dijkstra(search, |node_id, _| {
+            end.is_some_and(|end_node| end_node == node_id)
+        })
+    }
 
-            if end.is_some_and(|end_node| *end_node == u_node_id) {
-                return d_res;
+    fn run_dijkstra<V, Q, S>(
+        &self,
+        mut search: DijkstraIter<T::NodeIdType, GraphSuccessors<'_, T>, V, Q>,
+        mut stop: S,
+    ) -> Dijkstra<T::NodeIdType>
+    where
+        V: DijkstraVisitor<T::NodeIdType>,
+        Q: PriorityQueue<T::NodeIdType>,
+        S: FnMut(&T::NodeIdType, usize) -> bool,
+    {
+        for (node_id, dist) in search.by_ref() {
+            if stop(&node_id, dist) {
+                break;
             }
+        }
 
-            let dist_u = d_res.dist.get(&u_node_id).unwrap().clone();
-
-            if distance.0 > dist_u {
-                continue;
-            }
+        self.with_unreached(search.into_result())
+    }
 
-            for e in self.adjacency_list.get(&u_node_id).unwrap() {
-                let alt = dist_u + e.cost;
-                let v_node = e.node.clone();
-                if alt < *d_res.dist.get(&v_node).unwrap() {
-                    d_res.prev.entry(v_node.clone()).and_modify(|x| {
-                        x.replace(u_node_id.clone());
-                    });
-
-                    d_res.dist.entry(v_node.clone()).and_modify(|x| {
-                        *x = alt;
-                    });
-                    q.push(QueueEntry {
-                        node_id: v_node,
-                        distance: Reverse(alt),
-                    });
-                }
-            }
+    // nodes that were never reached are reported with an infinite distance
+    pub(crate) fn with_unreached(
+        &self,
+        mut d_res: Dijkstra<T::NodeIdType>,
+    ) -> Dijkstra<T::NodeIdType> {
+        for v in self.nodes.keys() {
+            d_res.prev.entry(v.clone()).or_insert(None);
+            d_res.dist.entry(v.clone()).or_insert(usize::MAX);
         }
 
-        return d_res;
+        d_res
     }
 }
 
@@ -198,68 +336,10 @@ where
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashSet, fmt::Display};
+    use std::collections::HashSet;
 
     use super::*;
-
-    #[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
-    struct TestNodeId(usize);
-
-    impl Display for TestNodeId {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            f.write_fmt(format_args!("{}", self.0))
-        }
-    }
-
-    impl IdTrait for TestNodeId {}
-
-    #[derive(Debug, Clone)]
-    struct TestNode {
-        id: TestNodeId,
-    }
-
-    impl Node for TestNode {
-        type NodeIdType = TestNodeId;
-
-        fn node_id(&self) -> Self::NodeIdType {
-            self.id.clone()
-        }
-    }
-
-    fn create_test_graph(edges: Vec<(usize, usize, usize)>) -> Graph<TestNode> {
-        let mut nodes = HashMap::new();
-        let mut adjacency_list: HashMap<TestNodeId, Vec<Edge<TestNode>>> = HashMap::new();
-
-        // Collect all unique node IDs
-        let mut node_ids: Vec<usize> = edges
-            .iter()
-            .flat_map(|(from, to, _)| vec![*from, *to])
-            .collect::<std::collections::HashSet<_>>()
-            .into_iter()
-            .collect();
-        node_ids.sort();
-
-        // Create nodes
-        for id in node_ids {
-            let node_id = TestNodeId(id);
-            nodes.insert(node_id.clone(), TestNode {
-                id: node_id.clone(),
-            });
-            adjacency_list.insert(node_id, Vec::new());
-        }
-
-        // Add edges
-        for (from, to, cost) in edges {
-            let from_id = TestNodeId(from);
-            let to_id = TestNodeId(to);
-            adjacency_list
-                .get_mut(&from_id)
-                .unwrap()
-                .push(Edge { node: to_id, cost });
-        }
-
-        Graph::new(nodes, adjacency_list)
-    }
+    use crate::test_graphs::*;
 
     #[test]
     fn test_simple_path() {
@@ -400,4 +480,76 @@ mod tests {
             TestNodeId(7)
         ]);
     }
+
+    #[derive(Default)]
+    struct CountingVisitor {
+        discovered: Vec<TestNodeId>,
+        settled: Vec<(TestNodeId, usize)>,
+        relaxed: usize,
+    }
+
+    impl DijkstraVisitor<TestNodeId> for CountingVisitor {
+        fn node_discovered(&mut self, node: &TestNodeId, _dist: usize) {
+            self.discovered.push(node.clone());
+        }
+
+        fn edge_relaxed(&mut self, _from: &TestNodeId, _to: &TestNodeId, _dist: usize) {
+            self.relaxed += 1;
+        }
+
+        fn node_settled(&mut self, node: &TestNodeId, dist: usize) {
+            self.settled.push((node.clone(), dist));
+        }
+    }
+
+    #[test]
+    fn test_visitor_callbacks() {
+        let graph = create_test_graph(vec![(0, 1, 4), (1, 2, 3), (0, 2, 8), (3, 0, 1)]);
+        let mut visitor = CountingVisitor::default();
+        graph.dijkstra_with(&TestNodeId(0), &mut visitor, |_, _| false);
+
+        // 3 is never reachable from 0
+        assert_eq!(
+            visitor.discovered,
+            vec![TestNodeId(0), TestNodeId(1), TestNodeId(2)]
+        );
+        assert_eq!(visitor.settled, vec![
+            (TestNodeId(0), 0),
+            (TestNodeId(1), 4),
+            (TestNodeId(2), 7)
+        ]);
+        // 0->1, 0->2 and then the improvement 1->2
+        assert_eq!(visitor.relaxed, 3);
+    }
+
+    #[test]
+    fn test_stop_on_property() {
+        let graph = create_test_graph(vec![(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 4, 1)]);
+        let mut visitor = CountingVisitor::default();
+        let result = graph.dijkstra_with(&TestNodeId(0), &mut visitor, |node_id, _| {
+            node_id.0 % 2 == 1
+        });
+
+        // the search ended on 1 without relaxing its edges
+        assert_eq!(*result.dist.get(&TestNodeId(1)).unwrap(), 1);
+        assert_eq!(*result.dist.get(&TestNodeId(2)).unwrap(), usize::MAX);
+        assert_eq!(visitor.settled.last(), Some(&(TestNodeId(1), 1)));
+    }
+
+    #[test]
+    fn test_stop_on_radius() {
+        let graph = create_test_graph(vec![(0, 1, 2), (1, 2, 2), (2, 3, 2), (0, 4, 5)]);
+        let mut visitor = CountingVisitor::default();
+        graph.dijkstra_with(&TestNodeId(0), &mut visitor, |_, dist| dist > 3);
+
+        let within_radius: Vec<_> = visitor
+            .settled
+            .iter()
+            .filter(|(_, dist)| *dist <= 3)
+            .map(|(node_id, _)| node_id.clone())
+            .collect();
+        assert_eq!(within_radius, vec![TestNodeId(0), TestNodeId(1)]);
+        // the first node outside the radius is reported, nothing further
+        assert_eq!(visitor.settled.len(), 3);
+    }
 }
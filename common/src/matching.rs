@@ -0,0 +1,280 @@
+use std::collections::VecDeque;
+use std::fmt::{Debug, Display};
+
+use crate::{IdTrait, UndirectedView};
+
+/// Returned when a graph has no two-colouring. `edge` connects two nodes that would need the same
+/// colour.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotBipartite<N> {
+    pub edge: (N, N),
+}
+
+impl<N> Display for NotBipartite<N>
+where
+    N: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "graph is not bipartite, see edge {:?}", self.edge)
+    }
+}
+
+impl<N> std::error::Error for NotBipartite<N> where N: Debug {}
+
+/// The two sides of a bipartite graph, both sorted. Every edge has one end on each side.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bipartition<N> {
+    pub left: Vec<N>,
+    pub right: Vec<N>,
+}
+
+impl<N> UndirectedView<N>
+where
+    N: IdTrait,
+{
+    /// Two-colours the view. In every connected component the smallest node goes on the left.
+    pub fn bipartition(&self) -> Result<Bipartition<N>, NotBipartite<N>> {
+        let is_left = self.two_coloring()?;
+        let (left, right): (Vec<_>, Vec<_>) = (0..self.adj().len()).partition(|&i| is_left[i]);
+        Ok(Bipartition {
+            left: left.into_iter().map(|i| self.id(i).clone()).collect(),
+            right: right.into_iter().map(|i| self.id(i).clone()).collect(),
+        })
+    }
+
+    /// Maximum matching of the view (Hopcroft-Karp), with the sides picked by
+    /// [`UndirectedView::bipartition`]. Pairs are `(left, right)`, sorted by the left node.
+    pub fn maximum_matching(&self) -> Result<Vec<(N, N)>, NotBipartite<N>> {
+        let is_left = self.two_coloring()?;
+        Ok(self.hopcroft_karp(&is_left))
+    }
+
+    /// Maximum matching where the caller decides which nodes are on the left, e.g. workers versus
+    /// jobs. Fails if an edge connects two nodes on the same side.
+    pub fn maximum_matching_with<F>(&self, is_left: F) -> Result<Vec<(N, N)>, NotBipartite<N>>
+    where
+        F: Fn(&N) -> bool,
+    {
+        let is_left: Vec<_> = self.nodes().iter().map(is_left).collect();
+        for (i, neighbors) in self.adj().iter().enumerate() {
+            if let Some(&j) = neighbors.iter().find(|&&j| is_left[i] == is_left[j]) {
+                return Err(NotBipartite {
+                    edge: (self.id(i).clone(), self.id(j).clone()),
+                });
+            }
+        }
+        Ok(self.hopcroft_karp(&is_left))
+    }
+
+    fn two_coloring(&self) -> Result<Vec<bool>, NotBipartite<N>> {
+        let adj = self.adj();
+        let mut color: Vec<Option<bool>> = vec![None; adj.len()];
+
+        for root in 0..adj.len() {
+            if color[root].is_some() {
+                continue;
+            }
+
+            color[root] = Some(true);
+            let mut q = VecDeque::from([root]);
+            while let Some(u) = q.pop_front() {
+                let c = color[u].unwrap();
+                for &v in &adj[u] {
+                    match color[v] {
+                        None => {
+                            color[v] = Some(!c);
+                            q.push_back(v);
+                        }
+                        Some(cv) if cv == c => {
+                            return Err(NotBipartite {
+                                edge: (self.id(u.min(v)).clone(), self.id(u.max(v)).clone()),
+                            });
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+
+        Ok(color.into_iter().map(|c| c.unwrap()).collect())
+    }
+
+    fn hopcroft_karp(&self, is_left: &[bool]) -> Vec<(N, N)> {
+        const FREE: usize = usize::MAX;
+
+        let adj = self.adj();
+        let left: Vec<_> = (0..adj.len()).filter(|&i| is_left[i]).collect();
+        let mut mate = vec![FREE; adj.len()];
+        let mut layer = vec![usize::MAX; adj.len()];
+
+        loop {
+            // bfs from every free left node, layering the left nodes by alternating path length
+            let mut q = VecDeque::new();
+            for &u in &left {
+                if mate[u] == FREE {
+                    layer[u] = 0;
+                    q.push_back(u);
+                } else {
+                    layer[u] = usize::MAX;
+                }
+            }
+
+            let mut found_free_right = false;
+            while let Some(u) = q.pop_front() {
+                for &v in &adj[u] {
+                    match mate[v] {
+                        FREE => found_free_right = true,
+                        w if layer[w] == usize::MAX => {
+                            layer[w] = layer[u] + 1;
+                            q.push_back(w);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            if !found_free_right {
+                break;
+            }
+
+            // dfs along the layers, flipping every vertex-disjoint shortest augmenting path
+            for &u in &left {
+                if mate[u] == FREE {
+                    augment(adj, u, &mut mate, &mut layer);
+                }
+            }
+        }
+
+        left.into_iter()
+            .filter(|&u| mate[u] != FREE)
+            .map(|u| (self.id(u).clone(), self.id(mate[u]).clone()))
+            .collect()
+    }
+}
+
+// dfs along the layers from the free left node `root`, done iteratively so long augmenting paths
+// don't blow the stack. Flips the path and returns true if it reaches a free right node.
+fn augment(adj: &[Vec<usize>], root: usize, mate: &mut [usize], layer: &mut [usize]) -> bool {
+    // (left node, index of the next neighbour to look at)
+    let mut stack = vec![(root, 0)];
+    while let Some(&(u, next)) = stack.last() {
+        let Some(&v) = adj[u].get(next) else {
+            // dead end, don't look here again during this phase
+            layer[u] = usize::MAX;
+            stack.pop();
+            continue;
+        };
+        stack.last_mut().unwrap().1 += 1;
+
+        let w = mate[v];
+        if w == usize::MAX {
+            // every left node on the stack takes the right node it was last looking at
+            for &(u, next) in &stack {
+                let v = adj[u][next - 1];
+                mate[u] = v;
+                mate[v] = u;
+            }
+            return true;
+        }
+        if layer[w] == layer[u] + 1 {
+            stack.push((w, 0));
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_graphs::*;
+
+    #[test]
+    fn test_bipartition() {
+        // a square 0-1-2-3 and a separate edge 4-5
+        let graph = create_test_graph(vec![(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 0, 1), (5, 4, 1)]);
+
+        assert_eq!(
+            graph.undirected().bipartition(),
+            Ok(Bipartition {
+                left: ids(&[0, 2, 4]),
+                right: ids(&[1, 3, 5])
+            })
+        );
+    }
+
+    #[test]
+    fn test_not_bipartite() {
+        let graph = create_test_graph(vec![(0, 1, 1), (1, 2, 1), (2, 0, 1)]);
+        let view = graph.undirected();
+
+        assert_eq!(
+            view.bipartition(),
+            Err(NotBipartite {
+                edge: (TestNodeId(1), TestNodeId(2))
+            })
+        );
+        assert!(view.maximum_matching().is_err());
+    }
+
+    #[test]
+    fn test_maximum_matching() {
+        // workers 0..3 and jobs 10..13, greedily matching 0-10 would leave 1 without a job
+        let graph = create_test_graph(vec![
+            (0, 10, 1),
+            (0, 11, 1),
+            (1, 10, 1),
+            (2, 10, 1),
+            (2, 12, 1),
+            (3, 12, 1),
+            (3, 13, 1),
+        ]);
+        let matching = graph
+            .undirected()
+            .maximum_matching_with(|n| n.0 < 10)
+            .unwrap();
+
+        assert_eq!(matching.len(), 4);
+        assert_eq!(
+            matching,
+            vec![
+                (TestNodeId(0), TestNodeId(11)),
+                (TestNodeId(1), TestNodeId(10)),
+                (TestNodeId(2), TestNodeId(12)),
+                (TestNodeId(3), TestNodeId(13))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_maximum_matching_not_perfect() {
+        // three workers competing for the same two jobs
+        let graph = create_test_graph(vec![(0, 10, 1), (1, 10, 1), (2, 10, 1), (2, 11, 1)]);
+        let view = graph.undirected();
+
+        assert_eq!(view.maximum_matching().unwrap().len(), 2);
+        assert_eq!(
+            view.maximum_matching_with(|n| n.0 == 0 || n.0 == 10),
+            Err(NotBipartite {
+                edge: (TestNodeId(0), TestNodeId(10))
+            })
+        );
+    }
+
+    #[test]
+    fn test_long_augmenting_path() {
+        // left i is joined to right i and i + 1, preferring i + 1 (the right ids count down). That
+        // matches greedily except for the last left node, which needs a path through all the others.
+        let n = 50_000;
+        let right = |i: usize| 2 * n - i;
+        let mut edges: Vec<_> = (0..n - 1).map(|i| (i, right(i + 1), 1)).collect();
+        edges.extend((0..n).map(|i| (i, right(i), 1)));
+        let graph = create_test_graph(edges);
+
+        let matching = graph
+            .undirected()
+            .maximum_matching_with(|node| node.0 < n)
+            .unwrap();
+        assert_eq!(matching.len(), n);
+        assert_eq!(matching[0], (TestNodeId(0), TestNodeId(right(0))));
+    }
+}
@@ -0,0 +1,235 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+
+use crate::{Graph, IdTrait, Node, UndirectedView};
+
+impl<T> Graph<T>
+where
+    T: Node,
+{
+    /// The largest shortest-path distance from `node` to any other node.
+    ///
+    /// Returns `None` if some node cannot be reached from `node` (or `node` is not in the graph).
+    pub fn eccentricity(&self, node: &T::NodeIdType) -> Option<usize> {
+        let indexed = self.indexed();
+        let source = *indexed.index.get(node)?;
+        let dist = indexed.distances_from(source);
+        dist.into_iter().try_fold(0, |acc, d| {
+            if d == usize::MAX {
+                None
+            } else {
+                Some(acc.max(d))
+            }
+        })
+    }
+
+    /// The eccentricity of every node, running one shortest-path search per node.
+    pub fn eccentricities(&self) -> HashMap<T::NodeIdType, Option<usize>> {
+        let indexed = self.indexed();
+        (0..indexed.len())
+            .map(|source| {
+                let max = indexed.distances_from(source).into_iter().max();
+                let ecc = max.filter(|&d| d != usize::MAX);
+                (indexed.ids[source].clone(), ecc)
+            })
+            .collect()
+    }
+
+    /// The largest eccentricity, `None` if the graph is empty or not strongly connected.
+    pub fn diameter(&self) -> Option<usize> {
+        let eccentricities: Option<Vec<_>> = self.eccentricities().into_values().collect();
+        eccentricities?.into_iter().max()
+    }
+
+    /// The smallest eccentricity, `None` if the graph is empty or not strongly connected.
+    pub fn radius(&self) -> Option<usize> {
+        let eccentricities: Option<Vec<_>> = self.eccentricities().into_values().collect();
+        eccentricities?.into_iter().min()
+    }
+
+    /// Betweenness centrality of every node (Brandes' algorithm, weighted and directed).
+    ///
+    /// The value of a node is the sum, over all ordered pairs `(s, t)` of other nodes, of the
+    /// fraction of shortest `s -> t` paths passing through it. Values are not normalised.
+    pub fn betweenness_centrality(&self) -> HashMap<T::NodeIdType, f64> {
+        let indexed = self.indexed();
+        let n = indexed.len();
+        let mut centrality = vec![0.0; n];
+
+        for s in 0..n {
+            // a dijkstra of its own rather than `DijkstraIter`: besides the distances it has to
+            // count every shortest path (`sigma`) and remember all of their predecessors (`preds`)
+            let mut settled = Vec::with_capacity(n);
+            let mut preds: Vec<Vec<usize>> = vec![vec![]; n];
+            let mut sigma = vec![0.0; n];
+            let mut dist = vec![usize::MAX; n];
+            let mut q = BinaryHeap::new();
+
+            sigma[s] = 1.0;
+            dist[s] = 0;
+            q.push(Reverse((0, s)));
+
+            while let Some(Reverse((d, v))) = q.pop() {
+                if d > dist[v] {
+                    continue;
+                }
+                settled.push(v);
+
+                for &(w, cost) in &indexed.adj[v] {
+                    let alt = d + cost;
+                    if alt < dist[w] {
+                        dist[w] = alt;
+                        q.push(Reverse((alt, w)));
+                        sigma[w] = 0.0;
+                        preds[w].clear();
+                    }
+                    if alt == dist[w] {
+                        sigma[w] += sigma[v];
+                        preds[w].push(v);
+                    }
+                }
+            }
+
+            // accumulate dependencies, furthest nodes first
+            let mut delta = vec![0.0; n];
+            while let Some(w) = settled.pop() {
+                for &v in &preds[w] {
+                    delta[v] += sigma[v] / sigma[w] * (1.0 + delta[w]);
+                }
+                if w != s {
+                    centrality[w] += delta[w];
+                }
+            }
+        }
+
+        indexed.ids.into_iter().zip(centrality).collect()
+    }
+
+    /// Number of nodes for every out-degree (counting parallel edges and self-loops).
+    pub fn out_degree_histogram(&self) -> BTreeMap<usize, usize> {
+        let indexed = self.indexed();
+        degree_histogram(indexed.adj.iter().map(|edges| edges.len()))
+    }
+
+    /// Number of nodes for every in-degree (counting parallel edges and self-loops).
+    pub fn in_degree_histogram(&self) -> BTreeMap<usize, usize> {
+        let indexed = self.indexed();
+        let mut in_degree = vec![0; indexed.len()];
+        for &(to, _) in indexed.adj.iter().flatten() {
+            in_degree[to] += 1;
+        }
+        degree_histogram(in_degree)
+    }
+}
+
+impl<N> UndirectedView<N>
+where
+    N: IdTrait,
+{
+    /// Number of nodes for every degree.
+    pub fn degree_histogram(&self) -> BTreeMap<usize, usize> {
+        degree_histogram(self.adj().iter().map(|adj| adj.len()))
+    }
+}
+
+fn degree_histogram(degrees: impl IntoIterator<Item = usize>) -> BTreeMap<usize, usize> {
+    let mut histogram = BTreeMap::new();
+    for degree in degrees {
+        *histogram.entry(degree).or_default() += 1;
+    }
+    histogram
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_graphs::*;
+
+    // undirected edges, stored in both directions
+    fn both_ways(edges: &[(usize, usize, usize)]) -> Vec<(usize, usize, usize)> {
+        edges
+            .iter()
+            .flat_map(|&(a, b, c)| [(a, b, c), (b, a, c)])
+            .collect()
+    }
+
+    #[test]
+    fn test_eccentricity_diameter_radius() {
+        // path 0 - 1 - 2 - 3 with a spur 1 - 4
+        let graph = create_test_graph(both_ways(&[(0, 1, 1), (1, 2, 1), (2, 3, 1), (1, 4, 1)]));
+
+        assert_eq!(graph.eccentricity(&TestNodeId(0)), Some(3));
+        assert_eq!(graph.eccentricity(&TestNodeId(1)), Some(2));
+        assert_eq!(graph.eccentricity(&TestNodeId(2)), Some(2));
+        assert_eq!(graph.eccentricity(&TestNodeId(9)), None);
+        assert_eq!(graph.diameter(), Some(3));
+        assert_eq!(graph.radius(), Some(2));
+    }
+
+    #[test]
+    fn test_eccentricity_weighted_and_disconnected() {
+        let graph = create_test_graph(vec![(0, 1, 4), (1, 2, 3), (0, 2, 8), (2, 0, 1)]);
+
+        assert_eq!(graph.eccentricity(&TestNodeId(0)), Some(7));
+        assert_eq!(graph.eccentricity(&TestNodeId(2)), Some(5));
+        assert_eq!(graph.diameter(), Some(7));
+        assert_eq!(graph.radius(), Some(4));
+
+        // 3 can't get anywhere
+        let graph = create_test_graph(vec![(0, 1, 1), (1, 0, 1), (1, 3, 1)]);
+        assert_eq!(graph.eccentricity(&TestNodeId(0)), Some(2));
+        assert_eq!(graph.eccentricities()[&TestNodeId(3)], None);
+        assert_eq!(graph.diameter(), None);
+        assert_eq!(graph.radius(), None);
+    }
+
+    #[test]
+    fn test_betweenness_centrality() {
+        // a corridor 0 - 1 - 2: every path between 0 and 2 goes through 1
+        let graph = create_test_graph(both_ways(&[(0, 1, 1), (1, 2, 1)]));
+        let centrality = graph.betweenness_centrality();
+
+        assert_eq!(centrality[&TestNodeId(0)], 0.0);
+        assert_eq!(centrality[&TestNodeId(1)], 2.0);
+        assert_eq!(centrality[&TestNodeId(2)], 0.0);
+    }
+
+    #[test]
+    fn test_betweenness_centrality_split_paths() {
+        // two equally short ways from 0 to 3, one much longer way through 4
+        let graph = create_test_graph(vec![
+            (0, 1, 1),
+            (1, 3, 1),
+            (0, 2, 1),
+            (2, 3, 1),
+            (0, 4, 5),
+            (4, 3, 5),
+        ]);
+        let centrality = graph.betweenness_centrality();
+
+        assert_eq!(centrality[&TestNodeId(1)], 0.5);
+        assert_eq!(centrality[&TestNodeId(2)], 0.5);
+        assert_eq!(centrality[&TestNodeId(4)], 0.0);
+    }
+
+    #[test]
+    fn test_degree_histograms() {
+        let graph = create_test_graph(vec![(0, 1, 1), (0, 2, 1), (0, 3, 1), (1, 2, 1)]);
+
+        assert_eq!(
+            graph.out_degree_histogram().into_iter().collect::<Vec<_>>(),
+            vec![(0, 2), (1, 1), (3, 1)]
+        );
+        assert_eq!(
+            graph.in_degree_histogram().into_iter().collect::<Vec<_>>(),
+            vec![(0, 1), (1, 2), (2, 1)]
+        );
+        assert_eq!(
+            graph
+                .undirected()
+                .degree_histogram()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![(1, 1), (2, 2), (3, 1)]
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::{Dijkstra, Graph, Node};
+
+impl<T> Graph<T>
+where
+    T: Node + Sync,
+    T::NodeIdType: Send + Sync,
+{
+    /// Runs a full [`Graph::dijkstra`] from every one of `sources`, spread over `threads` worker
+    /// threads (`0` means one per available core) that share this graph.
+    ///
+    /// Results are keyed by source, so the outcome doesn't depend on how the work got scheduled.
+    /// A source listed more than once is only searched once.
+    pub fn par_dijkstra(
+        &self,
+        sources: &[T::NodeIdType],
+        threads: usize,
+    ) -> BTreeMap<T::NodeIdType, Dijkstra<T::NodeIdType>> {
+        let mut sources = sources.to_vec();
+        sources.sort();
+        sources.dedup();
+
+        let threads = match threads {
+            0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            threads => threads,
+        }
+        .min(sources.len());
+
+        // workers grab the next source as soon as they're done with the previous one
+        let next_source = AtomicUsize::new(0);
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut results = vec![];
+                        loop {
+                            let i = next_source.fetch_add(1, Ordering::Relaxed);
+                            let Some(source) = sources.get(i) else {
+                                break;
+                            };
+                            results.push((source.clone(), self.dijkstra(source, None)));
+                        }
+                        results
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().unwrap())
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generators::{erdos_renyi, grid_graph};
+
+    #[test]
+    fn test_par_dijkstra_matches_sequential() {
+        let graph = grid_graph(20, 20, 1..=9, 5);
+        let sources: Vec<_> = (0..20).map(|i| (i, 19 - i)).collect();
+
+        for threads in [0, 1, 3, 64] {
+            let results = graph.par_dijkstra(&sources, threads);
+            assert_eq!(results.len(), sources.len());
+            for source in &sources {
+                assert_eq!(
+                    results[source].dist,
+                    graph.dijkstra(source, None).dist,
+                    "{threads} threads, source {source:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_par_dijkstra_ordering() {
+        let graph = erdos_renyi(40, 0.1, true, 1..=5, 9);
+        // 3 is listed twice, but only searched and returned once
+        let sources = [7, 3, 30, 3, 12];
+
+        let results = graph.par_dijkstra(&sources, 4);
+        assert_eq!(
+            results.keys().copied().collect::<Vec<_>>(),
+            vec![3, 7, 12, 30]
+        );
+        assert!(graph.par_dijkstra(&[], 4).is_empty());
+    }
+}
@@ -0,0 +1,381 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use crate::IdTrait;
+
+/// Min-priority queue used by [`crate::DijkstraIter`].
+///
+/// Dijkstra only ever pushes priorities that are at least the last popped one, so
+/// implementations are allowed to rely on that (monotone queues).
+pub trait PriorityQueue<N> {
+    fn push(&mut self, priority: usize, item: N);
+
+    /// Removes an item with the smallest priority.
+    fn pop(&mut self) -> Option<(usize, N)>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Everything currently queued, in no particular order.
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (usize, &'a N)>
+    where
+        N: 'a;
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct QueueEntry<N>
+where
+    N: IdTrait,
+{
+    distance: Reverse<usize>,
+    node_id: N,
+}
+
+/// `std`'s binary heap. Works for any priorities, `O(log n)` per operation. Among equal
+/// priorities the largest item is popped first.
+#[derive(Debug)]
+pub struct BinaryHeapQueue<N>
+where
+    N: IdTrait,
+{
+    heap: BinaryHeap<QueueEntry<N>>,
+}
+
+impl<N> Default for BinaryHeapQueue<N>
+where
+    N: IdTrait,
+{
+    fn default() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+        }
+    }
+}
+
+impl<N> PriorityQueue<N> for BinaryHeapQueue<N>
+where
+    N: IdTrait,
+{
+    fn push(&mut self, priority: usize, item: N) {
+        self.heap.push(QueueEntry {
+            distance: Reverse(priority),
+            node_id: item,
+        });
+    }
+
+    fn pop(&mut self) -> Option<(usize, N)> {
+        self.heap
+            .pop()
+            .map(|QueueEntry { distance, node_id }| (distance.0, node_id))
+    }
+
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (usize, &'a N)>
+    where
+        N: 'a,
+    {
+        self.heap.iter().map(|e| (e.distance.0, &e.node_id))
+    }
+}
+
+/// Monotone radix heap. Items are bucketed by the highest bit in which their priority differs
+/// from the last popped one, so each item is moved at most `usize::BITS` times.
+#[derive(Debug)]
+pub struct RadixHeap<N> {
+    buckets: Vec<Vec<(usize, N)>>,
+    last: usize,
+    len: usize,
+}
+
+impl<N> Default for RadixHeap<N> {
+    fn default() -> Self {
+        Self {
+            buckets: (0..=usize::BITS).map(|_| vec![]).collect(),
+            last: 0,
+            len: 0,
+        }
+    }
+}
+
+impl<N> RadixHeap<N> {
+    fn bucket(&self, priority: usize) -> usize {
+        (usize::BITS - (priority ^ self.last).leading_zeros()) as usize
+    }
+}
+
+impl<N> PriorityQueue<N> for RadixHeap<N> {
+    fn push(&mut self, priority: usize, item: N) {
+        assert!(
+            priority >= self.last,
+            "radix heap priorities must not go below the last popped one"
+        );
+        let bucket = self.bucket(priority);
+        self.buckets[bucket].push((priority, item));
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<(usize, N)> {
+        if self.buckets[0].is_empty() {
+            // refill bucket 0 from the first non-empty bucket, everything in there moves down
+            let i = self.buckets.iter().position(|b| !b.is_empty())?;
+            self.last = self.buckets[i].iter().map(|(p, _)| *p).min().unwrap();
+            for (priority, item) in std::mem::take(&mut self.buckets[i]) {
+                let bucket = self.bucket(priority);
+                self.buckets[bucket].push((priority, item));
+            }
+        }
+
+        self.len -= 1;
+        self.buckets[0].pop()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (usize, &'a N)>
+    where
+        N: 'a,
+    {
+        self.buckets.iter().flatten().map(|(p, item)| (*p, item))
+    }
+}
+
+/// Dial's bucket queue: a ring of `max_cost + 1` buckets, one per priority. Pushes and pops are
+/// `O(1)` (amortised over the scan for the next non-empty bucket), which pays off when edge costs
+/// are small integers.
+///
+/// Every pushed priority must be within `max_cost` of the last popped one.
+#[derive(Debug)]
+pub struct BucketQueue<N> {
+    buckets: Vec<Vec<N>>,
+    current: usize,
+    len: usize,
+}
+
+impl<N> BucketQueue<N> {
+    /// A queue for searches whose edges cost at most `max_cost`.
+    pub fn new(max_cost: usize) -> Self {
+        Self {
+            buckets: (0..=max_cost).map(|_| vec![]).collect(),
+            current: 0,
+            len: 0,
+        }
+    }
+}
+
+impl<N> PriorityQueue<N> for BucketQueue<N> {
+    fn push(&mut self, priority: usize, item: N) {
+        assert!(
+            priority >= self.current && priority - self.current < self.buckets.len(),
+            "priority {priority} is out of the bucket queue's window starting at {}",
+            self.current
+        );
+        let bucket = priority % self.buckets.len();
+        self.buckets[bucket].push(item);
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<(usize, N)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        loop {
+            let bucket = self.current % self.buckets.len();
+            if let Some(item) = self.buckets[bucket].pop() {
+                self.len -= 1;
+                return Some((self.current, item));
+            }
+            self.current += 1;
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (usize, &'a N)>
+    where
+        N: 'a,
+    {
+        // the buckets from `current` onwards hold consecutive priorities
+        let n = self.buckets.len();
+        (0..n).flat_map(move |offset| {
+            let priority = self.current + offset;
+            self.buckets[priority % n]
+                .iter()
+                .map(move |item| (priority, item))
+        })
+    }
+}
+
+/// Double-ended queue for 0-1 BFS: priorities equal to the last popped one go to the front, one
+/// more than it to the back. Anything else panics, so all edge costs must be 0 or 1.
+#[derive(Debug)]
+pub struct ZeroOneQueue<N> {
+    deque: VecDeque<(usize, N)>,
+    current: usize,
+}
+
+impl<N> Default for ZeroOneQueue<N> {
+    fn default() -> Self {
+        Self {
+            deque: VecDeque::new(),
+            current: 0,
+        }
+    }
+}
+
+impl<N> PriorityQueue<N> for ZeroOneQueue<N> {
+    fn push(&mut self, priority: usize, item: N) {
+        if priority == self.current {
+            self.deque.push_front((priority, item));
+        } else {
+            assert!(
+                priority == self.current + 1,
+                "0-1 queue priorities must be {} or {}, got {priority}",
+                self.current,
+                self.current + 1
+            );
+            self.deque.push_back((priority, item));
+        }
+    }
+
+    fn pop(&mut self) -> Option<(usize, N)> {
+        let (priority, item) = self.deque.pop_front()?;
+        self.current = priority;
+        Some((priority, item))
+    }
+
+    fn len(&self) -> usize {
+        self.deque.len()
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (usize, &'a N)>
+    where
+        N: 'a,
+    {
+        self.deque.iter().map(|(p, item)| (*p, item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::{erdos_renyi, grid_graph};
+    use crate::search::GraphSuccessors;
+    use crate::{DijkstraIter, Graph, PlainNode};
+
+    fn drain<Q: PriorityQueue<usize>>(mut queue: Q) -> Vec<usize> {
+        for (priority, item) in [(5, 50), (3, 30), (3, 31), (9, 90), (4, 40)] {
+            queue.push(priority, item);
+        }
+        assert_eq!(queue.len(), 5);
+
+        let mut popped = vec![];
+        let (priority, _) = queue.pop().unwrap();
+        popped.push(priority);
+
+        // pushing while popping, as dijkstra does
+        queue.push(priority + 1, 41);
+        queue.push(priority + 7, 100);
+        while let Some((priority, _)) = queue.pop() {
+            popped.push(priority);
+        }
+        assert!(queue.is_empty());
+        popped
+    }
+
+    fn settle_order<Q>(graph: &Graph<PlainNode<usize>>, queue: Q) -> Vec<usize>
+    where
+        Q: PriorityQueue<usize>,
+    {
+        DijkstraIter::with_queue(0, GraphSuccessors::new(graph), (), queue)
+            .map(|(_, dist)| dist)
+            .collect()
+    }
+
+    #[test]
+    fn test_queues_pop_in_order() {
+        let expected = vec![3, 3, 4, 4, 5, 9, 10];
+        assert_eq!(drain(BinaryHeapQueue::default()), expected);
+        assert_eq!(drain(RadixHeap::default()), expected);
+        assert_eq!(drain(BucketQueue::new(9)), expected);
+
+        let mut queue = ZeroOneQueue::default();
+        queue.push(0, 'a');
+        queue.push(1, 'b');
+        queue.push(0, 'c');
+        assert_eq!(queue.pop(), Some((0, 'c')));
+        assert_eq!(queue.pop(), Some((0, 'a')));
+        queue.push(1, 'd');
+        assert_eq!(queue.pop(), Some((1, 'b')));
+        queue.push(2, 'e');
+        assert_eq!(
+            queue.iter().map(|(p, c)| (p, *c)).collect::<Vec<_>>(),
+            vec![(1, 'd'), (2, 'e')]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_one_queue_cost() {
+        let mut queue = ZeroOneQueue::default();
+        queue.push(2, 'a');
+    }
+
+    #[test]
+    fn test_queue_iter() {
+        let mut queue = RadixHeap::default();
+        queue.push(4, 'a');
+        queue.push(9, 'b');
+        queue.pop();
+        queue.push(6, 'c');
+        let mut queued: Vec<_> = queue.iter().map(|(p, c)| (p, *c)).collect();
+        queued.sort();
+        assert_eq!(queued, vec![(6, 'c'), (9, 'b')]);
+
+        let mut queue = BucketQueue::new(3);
+        queue.push(2, 'a');
+        queue.push(3, 'b');
+        queue.pop();
+        queue.push(5, 'c');
+        assert_eq!(
+            queue.iter().map(|(p, c)| (p, *c)).collect::<Vec<_>>(),
+            vec![(3, 'b'), (5, 'c')]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bucket_queue_window() {
+        let mut queue = BucketQueue::new(3);
+        queue.push(4, 'a');
+    }
+
+    #[test]
+    fn test_queues_agree_on_distances() {
+        for seed in 0..10 {
+            let graph = erdos_renyi(60, 0.05, true, 1..=12, seed);
+            let expected = settle_order(&graph, BinaryHeapQueue::default());
+            assert_eq!(settle_order(&graph, RadixHeap::default()), expected);
+            assert_eq!(settle_order(&graph, BucketQueue::new(12)), expected);
+        }
+
+        let graph = grid_graph(30, 30, 1..=3, 1);
+        let expected = graph.dijkstra(&(0, 0), None).dist;
+        for queue_result in [
+            graph.dijkstra_using(&(0, 0), None, RadixHeap::default()),
+            graph.dijkstra_using(&(0, 0), None, BucketQueue::new(3)),
+        ] {
+            assert_eq!(queue_result.dist, expected);
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::VecDeque;
+
+use crate::{Grid, Vector};
+
+/// A 4-connected group of cells, see [`Grid::regions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub label: usize,
+    /// the cells of the region, row by row
+    pub cells: Vec<(usize, usize)>,
+    /// number of cell edges between the region and anything else (including the grid border)
+    pub perimeter: usize,
+    /// number of straight fence segments around the region, holes included
+    pub sides: usize,
+    /// inclusive corners of the bounding box
+    pub top_left: (usize, usize),
+    pub bottom_right: (usize, usize),
+    /// number of enclosed areas not belonging to the region, each of them 8-connected
+    pub holes: usize,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+/// Every region of a grid, labelled `0..len` in the order their first cell appears row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regions {
+    /// the label of the region every cell belongs to
+    pub labels: Grid<usize>,
+    pub regions: Vec<Region>,
+}
+
+impl Regions {
+    /// The region containing `pos`.
+    pub fn at(&self, pos: (usize, usize)) -> Option<&Region> {
+        self.labels.get(pos).map(|&label| &self.regions[label])
+    }
+}
+
+impl<T> Grid<T> {
+    /// Splits the grid into connected regions of equal cells, e.g. the plots of one plant type.
+    pub fn regions(&self) -> Regions
+    where
+        T: PartialEq,
+    {
+        self.regions_by(|a, b| a == b)
+    }
+
+    /// Splits the grid into regions, where two orthogonally adjacent cells belong together if
+    /// `same(a, b)` holds.
+    pub fn regions_by<F>(&self, same: F) -> Regions
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        const UNLABELLED: usize = usize::MAX;
+
+        let mut labels = Grid::new(self.width(), self.height(), UNLABELLED);
+        let mut members = vec![];
+        for (start, _) in self.cells() {
+            if labels[start] != UNLABELLED {
+                continue;
+            }
+
+            let label = members.len();
+            labels[start] = label;
+            let mut cells = vec![];
+            let mut q = VecDeque::from([start]);
+            while let Some(pos) = q.pop_front() {
+                cells.push(pos);
+                for next in self.neighbors4(pos) {
+                    if labels[next] == UNLABELLED && same(&self[pos], &self[next]) {
+                        labels[next] = label;
+                        q.push_back(next);
+                    }
+                }
+            }
+            cells.sort_by_key(|&(col, row)| (row, col));
+            members.push(cells);
+        }
+
+        let regions = members
+            .into_iter()
+            .enumerate()
+            .map(|(label, cells)| describe(&labels, label, cells))
+            .collect();
+        Regions { labels, regions }
+    }
+}
+
+fn describe(labels: &Grid<usize>, label: usize, cells: Vec<(usize, usize)>) -> Region {
+    let inside = |pos: (usize, usize), step: Vector| {
+        labels
+            .offset(pos, step)
+            .is_some_and(|next| labels[next] == label)
+    };
+
+    let mut perimeter = 0;
+    let mut sides = 0;
+    for &pos in &cells {
+        perimeter += Vector::NEIGHBORS4
+            .iter()
+            .filter(|&&step| !inside(pos, step))
+            .count();
+
+        // every corner of the fence starts a new side, check the four diagonal quadrants
+        for (dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+            let horizontal = inside(pos, Vector::new(dx, 0));
+            let vertical = inside(pos, Vector::new(0, dy));
+            let diagonal = inside(pos, Vector::new(dx, dy));
+            if (!horizontal && !vertical) || (horizontal && vertical && !diagonal) {
+                sides += 1;
+            }
+        }
+    }
+
+    let top_left = (
+        cells.iter().map(|p| p.0).min().unwrap(),
+        cells.iter().map(|p| p.1).min().unwrap(),
+    );
+    let bottom_right = (
+        cells.iter().map(|p| p.0).max().unwrap(),
+        cells.iter().map(|p| p.1).max().unwrap(),
+    );
+    let holes = count_holes(&cells, top_left, bottom_right);
+
+    Region {
+        label,
+        cells,
+        perimeter,
+        sides,
+        top_left,
+        bottom_right,
+        holes,
+    }
+}
+
+// flood fills the bounding box (plus a one cell margin) from the outside, whatever is left over
+// and not part of the region is enclosed
+fn count_holes(
+    cells: &[(usize, usize)],
+    (left, top): (usize, usize),
+    (right, bottom): (usize, usize),
+) -> usize {
+    let mut open = Grid::new(right - left + 3, bottom - top + 3, true);
+    for &(col, row) in cells {
+        open[(col - left + 1, row - top + 1)] = false;
+    }
+
+    let fill = |open: &mut Grid<bool>, start| {
+        open[start] = false;
+        let mut q = VecDeque::from([start]);
+        while let Some(pos) = q.pop_front() {
+            for next in open.neighbors8(pos).collect::<Vec<_>>() {
+                if open[next] {
+                    open[next] = false;
+                    q.push_back(next);
+                }
+            }
+        }
+    };
+
+    fill(&mut open, (0, 0));
+    let mut holes = 0;
+    for row in 0..open.height() {
+        for col in 0..open.width() {
+            if open[(col, row)] {
+                fill(&mut open, (col, row));
+                holes += 1;
+            }
+        }
+    }
+    holes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the garden examples from 2024 day 12
+    const SMALL: &str = "AAAA\nBBCD\nBBCC\nEEEC";
+    const NESTED: &str = "OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO";
+    const E_SHAPE: &str = "EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE";
+    const TOUCHING: &str = "AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA";
+
+    fn prices(map: &str) -> (usize, usize) {
+        let grid: Grid<char> = map.parse().unwrap();
+        let regions = grid.regions().regions;
+        (
+            regions.iter().map(|r| r.area() * r.perimeter).sum(),
+            regions.iter().map(|r| r.area() * r.sides).sum(),
+        )
+    }
+
+    #[test]
+    fn test_fence_prices() {
+        assert_eq!(prices(SMALL), (140, 80));
+        assert_eq!(prices(NESTED), (772, 436));
+        assert_eq!(prices(E_SHAPE).1, 236);
+        assert_eq!(prices(TOUCHING).1, 368);
+    }
+
+    #[test]
+    fn test_region_details() {
+        let grid: Grid<char> = SMALL.parse().unwrap();
+        let regions = grid.regions();
+        assert_eq!(regions.regions.len(), 5);
+        assert_eq!(regions.labels.row(1), Some(&[1, 1, 2, 3][..]));
+
+        let c = regions.at((2, 1)).unwrap();
+        assert_eq!(c.label, 2);
+        assert_eq!(c.cells, vec![(2, 1), (2, 2), (3, 2), (3, 3)]);
+        assert_eq!((c.area(), c.perimeter, c.sides), (4, 10, 8));
+        assert_eq!((c.top_left, c.bottom_right), ((2, 1), (3, 3)));
+        assert_eq!(c.holes, 0);
+
+        let grid: Grid<char> = NESTED.parse().unwrap();
+        let o = &grid.regions().regions[0];
+        assert_eq!((o.area(), o.holes), (21, 4));
+
+        // the two B blocks touch diagonally, so together they make up a single hole
+        let grid: Grid<char> = TOUCHING.parse().unwrap();
+        assert_eq!(grid.regions().regions[0].holes, 1);
+    }
+
+    #[test]
+    fn test_regions_by() {
+        // 1 and 2 are both land, enclosing the water at (1, 1)
+        let grid: Grid<u8> = "2110\n1021\n1210\n0000".parse().unwrap();
+        let regions = grid.regions_by(|a, b| (*a > 0) == (*b > 0));
+
+        let land = regions.at((0, 0)).unwrap();
+        assert_eq!((land.area(), land.holes), (9, 1));
+        assert_eq!(regions.at((1, 1)).unwrap().area(), 1);
+        assert_eq!(regions.regions.len(), 4);
+
+        // a diagonal gap is enough to let the water out
+        let grid: Grid<u8> = "2110\n1021\n1200\n0000".parse().unwrap();
+        let regions = grid.regions_by(|a, b| (*a > 0) == (*b > 0));
+        let land = regions.at((0, 0)).unwrap();
+        assert_eq!((land.area(), land.holes), (8, 0));
+    }
+}
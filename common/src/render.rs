@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::Grid;
+
+/// The 8 basic ANSI terminal colours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    fn ansi_code(self) -> u8 {
+        30 + self as u8
+    }
+}
+
+/// Positions drawn on top of the grid, e.g. robots, a path or visited cells.
+#[derive(Debug, Clone, Default)]
+pub struct Layer {
+    glyphs: HashMap<(usize, usize), char>,
+    color: Option<Color>,
+}
+
+impl Layer {
+    /// Draws `glyph` at every one of `positions`.
+    pub fn new<I>(glyph: char, positions: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize)>,
+    {
+        Self::from_glyphs(positions.into_iter().map(|pos| (pos, glyph)))
+    }
+
+    /// Draws a different glyph per position, e.g. arrows along a path.
+    pub fn from_glyphs<I>(glyphs: I) -> Self
+    where
+        I: IntoIterator<Item = ((usize, usize), char)>,
+    {
+        Self {
+            glyphs: glyphs.into_iter().collect(),
+            color: None,
+        }
+    }
+
+    pub fn color(self, color: Color) -> Self {
+        Self {
+            color: Some(color),
+            ..self
+        }
+    }
+}
+
+type CellColor<'a, T> = Box<dyn Fn(&T) -> Option<Color> + 'a>;
+
+/// Draws a [`Grid`] as text, one character per cell, with any number of [`Layer`]s on top
+/// (later layers win). Colours are only emitted with [`Renderer::colored`], so the plain output
+/// can be compared against golden files.
+pub struct Renderer<'a, T> {
+    grid: &'a Grid<T>,
+    glyph: Box<dyn Fn(&T) -> char + 'a>,
+    cell_color: CellColor<'a, T>,
+    layers: Vec<Layer>,
+    colored: bool,
+}
+
+impl<'a, T> Renderer<'a, T> {
+    /// Renders every cell as `glyph(cell)`.
+    pub fn new<G>(grid: &'a Grid<T>, glyph: G) -> Self
+    where
+        G: Fn(&T) -> char + 'a,
+    {
+        Self {
+            grid,
+            glyph: Box::new(glyph),
+            cell_color: Box::new(|_| None),
+            layers: vec![],
+            colored: false,
+        }
+    }
+
+    /// Colours the grid's own cells, only used when [`Renderer::colored`] is on.
+    pub fn cell_color<F>(self, cell_color: F) -> Self
+    where
+        F: Fn(&T) -> Option<Color> + 'a,
+    {
+        Self {
+            cell_color: Box::new(cell_color),
+            ..self
+        }
+    }
+
+    pub fn layer(mut self, layer: Layer) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Turns ANSI colour escapes on or off (the default).
+    pub fn colored(self, colored: bool) -> Self {
+        Self { colored, ..self }
+    }
+
+    /// Writes one line per row, each ending in a newline. Layer positions outside the grid are
+    /// ignored.
+    pub fn write_to<W>(&self, out: &mut W) -> io::Result<()>
+    where
+        W: Write + ?Sized,
+    {
+        for (row, cells) in self.grid.rows().enumerate() {
+            let mut current = None;
+            let mut line = String::new();
+            for (col, cell) in cells.iter().enumerate() {
+                let (glyph, color) = self
+                    .layers
+                    .iter()
+                    .rev()
+                    .find_map(|layer| layer.glyphs.get(&(col, row)).map(|&g| (g, layer.color)))
+                    .unwrap_or_else(|| ((self.glyph)(cell), (self.cell_color)(cell)));
+
+                // only switch colours when they change
+                if self.colored && color != current {
+                    match color {
+                        Some(color) => line.push_str(&format!("\x1b[{}m", color.ansi_code())),
+                        None => line.push_str("\x1b[0m"),
+                    }
+                    current = color;
+                }
+                line.push(glyph);
+            }
+            if current.is_some() {
+                line.push_str("\x1b[0m");
+            }
+            writeln!(out, "{line}")?;
+        }
+        Ok(())
+    }
+
+    /// The rendered frame as a string, see [`Renderer::write_to`].
+    pub fn render(&self) -> String {
+        let mut out = vec![];
+        self.write_to(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WAREHOUSE: &str = "\
+########
+#..O.O.#
+##@.O..#
+#...O..#
+########";
+
+    #[test]
+    fn test_plain() {
+        let grid: Grid<char> = WAREHOUSE.parse().unwrap();
+        let robot = grid.position(|&c| c == '@').unwrap();
+        let grid = grid.map(|&c| if c == '@' { '.' } else { c });
+
+        let frame = Renderer::new(&grid, |&c| c)
+            .layer(Layer::new('+', [(3, 3), (2, 3), (9, 9)]))
+            .layer(Layer::new('@', [robot]).color(Color::Red))
+            .render();
+        assert_eq!(
+            frame,
+            "\
+########
+#..O.O.#
+##@.O..#
+#.++O..#
+########
+"
+        );
+    }
+
+    #[test]
+    fn test_colored() {
+        let grid: Grid<char> = "#.\n.#".parse().unwrap();
+        let renderer = Renderer::new(&grid, |&c| c)
+            .cell_color(|&c| (c == '#').then_some(Color::Blue))
+            .layer(Layer::from_glyphs([((1, 0), '>')]).color(Color::Blue))
+            .colored(true);
+
+        let mut out = vec![];
+        renderer.write_to(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b[34m#>\x1b[0m\n.\x1b[34m#\x1b[0m\n"
+        );
+
+        // the same renderer without colours
+        assert_eq!(renderer.colored(false).render(), "#>\n.#\n");
+    }
+}
@@ -0,0 +1,523 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::queue::{BinaryHeapQueue, PriorityQueue, ZeroOneQueue};
+use crate::{Dijkstra, DijkstraVisitor, Graph, IdTrait, Node};
+
+/// Anything that can list the outgoing edges of a node.
+///
+/// Implemented for [`Graph`] references and for closures returning `(node, cost)` pairs, so the
+/// same search code runs over explicit graphs and implicit state spaces.
+pub trait Successors<N> {
+    fn for_each_successor<F>(&mut self, node: &N, f: F)
+    where
+        F: FnMut(N, usize);
+}
+
+impl<N, S, I> Successors<N> for S
+where
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    fn for_each_successor<F>(&mut self, node: &N, mut f: F)
+    where
+        F: FnMut(N, usize),
+    {
+        for (next, cost) in self(node) {
+            f(next, cost);
+        }
+    }
+}
+
+/// Adapter exposing the edges of a [`Graph`] as [`Successors`].
+#[derive(Debug, Clone, Copy)]
+pub struct GraphSuccessors<'a, T>
+where
+    T: Node,
+{
+    graph: &'a Graph<T>,
+}
+
+impl<'a, T> GraphSuccessors<'a, T>
+where
+    T: Node,
+{
+    pub fn new(graph: &'a Graph<T>) -> Self {
+        Self { graph }
+    }
+}
+
+impl<T> Successors<T::NodeIdType> for GraphSuccessors<'_, T>
+where
+    T: Node,
+{
+    fn for_each_successor<F>(&mut self, node: &T::NodeIdType, mut f: F)
+    where
+        F: FnMut(T::NodeIdType, usize),
+    {
+        for e in self.graph.adjacency_list.get(node).into_iter().flatten() {
+            f(e.node.clone(), e.cost);
+        }
+    }
+}
+
+/// Dijkstra as an iterator, yielding every settled node together with its final distance in the
+/// order they are settled.
+///
+/// A node's edges are only relaxed when the iterator is advanced past it, so stopping early (e.g.
+/// with `take`) leaves its neighbours untouched. The queue defaults to a binary heap, see
+/// [`PriorityQueue`] for the alternatives.
+pub struct DijkstraIter<N, S, V = (), Q = BinaryHeapQueue<N>>
+where
+    N: IdTrait,
+{
+    dist: HashMap<N, usize>,
+    prev: HashMap<N, Option<N>>,
+    queue: Q,
+    successors: S,
+    visitor: V,
+    last_settled: Option<(N, usize)>,
+}
+
+impl<N, S> DijkstraIter<N, S, ()>
+where
+    N: IdTrait,
+    S: Successors<N>,
+{
+    pub fn new(start: N, successors: S) -> Self {
+        Self::with_visitor(start, successors, ())
+    }
+}
+
+impl<N, S, V> DijkstraIter<N, S, V>
+where
+    N: IdTrait,
+    S: Successors<N>,
+    V: DijkstraVisitor<N>,
+{
+    pub fn with_visitor(start: N, successors: S, visitor: V) -> Self {
+        Self::with_queue(start, successors, visitor, BinaryHeapQueue::default())
+    }
+}
+
+impl<N, S, V, Q> DijkstraIter<N, S, V, Q>
+where
+    N: IdTrait,
+    S: Successors<N>,
+    V: DijkstraVisitor<N>,
+    Q: PriorityQueue<N>,
+{
+    pub fn with_queue(start: N, successors: S, mut visitor: V, mut queue: Q) -> Self {
+        visitor.node_discovered(&start, 0);
+        queue.push(0, start.clone());
+
+        Self {
+            dist: HashMap::from([(start.clone(), 0)]),
+            prev: HashMap::from([(start, None)]),
+            queue,
+            successors,
+            visitor,
+            last_settled: None,
+        }
+    }
+
+    /// Tentative (or final, for settled nodes) distances of every node reached so far.
+    pub fn dist(&self) -> &HashMap<N, usize> {
+        &self.dist
+    }
+
+    /// Predecessors of every node reached so far.
+    pub fn prev(&self) -> &HashMap<N, Option<N>> {
+        &self.prev
+    }
+
+    /// Nodes waiting in the queue with their tentative distance, ignoring outdated entries.
+    pub fn frontier(&self) -> impl Iterator<Item = (&N, usize)> + '_ {
+        let mut seen = HashSet::new();
+        self.queue
+            .iter()
+            .filter(|(distance, node_id)| self.dist.get(node_id) == Some(distance))
+            .filter(move |(_, node_id)| seen.insert(*node_id))
+            .map(|(distance, node_id)| (node_id, distance))
+    }
+
+    /// Consumes the search, returning everything computed up to this point.
+    pub fn into_result(self) -> Dijkstra<N> {
+        Dijkstra {
+            dist: self.dist,
+            prev: self.prev,
+        }
+    }
+
+    fn relax_edges(&mut self, u_node_id: N, dist_u: usize) {
+        let Self {
+            dist,
+            prev,
+            queue,
+            successors,
+            visitor,
+            ..
+        } = self;
+
+        successors.for_each_successor(&u_node_id, |v_node, cost| {
+            let alt = dist_u + cost;
+            let dist_v = dist.get(&v_node).copied().unwrap_or(usize::MAX);
+            if alt < dist_v {
+                if dist_v == usize::MAX {
+                    visitor.node_discovered(&v_node, alt);
+                }
+                visitor.edge_relaxed(&u_node_id, &v_node, alt);
+
+                prev.insert(v_node.clone(), Some(u_node_id.clone()));
+                dist.insert(v_node.clone(), alt);
+                queue.push(alt, v_node);
+            }
+        });
+    }
+}
+
+impl<N, S, V, Q> Iterator for DijkstraIter<N, S, V, Q>
+where
+    N: IdTrait,
+    S: Successors<N>,
+    V: DijkstraVisitor<N>,
+    Q: PriorityQueue<N>,
+{
+    type Item = (N, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // expand the node we handed out last time before looking for the next one
+        if let Some((u_node_id, dist_u)) = self.last_settled.take() {
+            self.relax_edges(u_node_id, dist_u);
+        }
+
+        while let Some((distance, node_id)) = self.queue.pop() {
+            let dist_u = self.dist[&node_id];
+            if distance > dist_u {
+                continue;
+            }
+
+            self.visitor.node_settled(&node_id, dist_u);
+            self.last_settled = Some((node_id.clone(), dist_u));
+            return Some((node_id, dist_u));
+        }
+
+        None
+    }
+}
+
+/// Breadth-first search as an iterator, yielding every node with its distance in hops (edge costs
+/// are ignored) in the order it is dequeued.
+pub struct BfsIter<N, S>
+where
+    N: IdTrait,
+{
+    dist: HashMap<N, usize>,
+    prev: HashMap<N, Option<N>>,
+    queue: VecDeque<N>,
+    successors: S,
+    last_visited: Option<(N, usize)>,
+}
+
+impl<N, S> BfsIter<N, S>
+where
+    N: IdTrait,
+    S: Successors<N>,
+{
+    pub fn new(start: N, successors: S) -> Self {
+        Self {
+            dist: HashMap::from([(start.clone(), 0)]),
+            prev: HashMap::from([(start.clone(), None)]),
+            queue: VecDeque::from([start]),
+            successors,
+            last_visited: None,
+        }
+    }
+
+    /// Distances of every node discovered so far.
+    pub fn dist(&self) -> &HashMap<N, usize> {
+        &self.dist
+    }
+
+    /// Predecessors of every node discovered so far.
+    pub fn prev(&self) -> &HashMap<N, Option<N>> {
+        &self.prev
+    }
+
+    /// Nodes discovered but not yet yielded, in the order they will be yielded.
+    pub fn frontier(&self) -> impl Iterator<Item = (&N, usize)> + '_ {
+        self.queue
+            .iter()
+            .map(|node_id| (node_id, self.dist[node_id]))
+    }
+
+    /// Consumes the search, returning everything computed up to this point.
+    pub fn into_result(self) -> Dijkstra<N> {
+        Dijkstra {
+            dist: self.dist,
+            prev: self.prev,
+        }
+    }
+}
+
+impl<N, S> Iterator for BfsIter<N, S>
+where
+    N: IdTrait,
+    S: Successors<N>,
+{
+    type Item = (N, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((u_node_id, dist_u)) = self.last_visited.take() {
+            let Self {
+                dist,
+                prev,
+                queue,
+                successors,
+                ..
+            } = self;
+            successors.for_each_successor(&u_node_id, |v_node, _| {
+                if !dist.contains_key(&v_node) {
+                    dist.insert(v_node.clone(), dist_u + 1);
+                    prev.insert(v_node.clone(), Some(u_node_id.clone()));
+                    queue.push_back(v_node);
+                }
+            });
+        }
+
+        let node_id = self.queue.pop_front()?;
+        let dist_u = self.dist[&node_id];
+        self.last_visited = Some((node_id.clone(), dist_u));
+        Some((node_id, dist_u))
+    }
+}
+
+/// Step-wise dijkstra over an implicit graph described by `successors`.
+pub fn dijkstra_iter<N, S>(start: N, successors: S) -> DijkstraIter<N, S>
+where
+    N: IdTrait,
+    S: Successors<N>,
+{
+    DijkstraIter::new(start, successors)
+}
+
+/// Step-wise breadth-first search over an implicit graph described by `successors`.
+pub fn bfs_iter<N, S>(start: N, successors: S) -> BfsIter<N, S>
+where
+    N: IdTrait,
+    S: Successors<N>,
+{
+    BfsIter::new(start, successors)
+}
+
+/// Breadth-first search over an implicit graph, counting hops and ignoring edge costs. Stops once
+/// `end` is reached, otherwise explores everything reachable from `start`.
+pub fn bfs<N, S>(start: N, end: Option<&N>, successors: S) -> Dijkstra<N>
+where
+    N: IdTrait,
+    S: Successors<N>,
+{
+    let mut search = BfsIter::new(start, successors);
+    for (node_id, _) in search.by_ref() {
+        if end.is_some_and(|end_node| *end_node == node_id) {
+            break;
+        }
+    }
+    search.into_result()
+}
+
+/// Shortest paths over an implicit graph whose edges all cost 0 or 1, using a deque instead of a
+/// heap. Panics on any other cost.
+pub fn zero_one_bfs<N, S>(start: N, end: Option<&N>, successors: S) -> Dijkstra<N>
+where
+    N: IdTrait,
+    S: Successors<N>,
+{
+    let mut search = DijkstraIter::with_queue(start, successors, (), ZeroOneQueue::default());
+    for (node_id, _) in search.by_ref() {
+        if end.is_some_and(|end_node| *end_node == node_id) {
+            break;
+        }
+    }
+    search.into_result()
+}
+
+impl<T> Graph<T>
+where
+    T: Node,
+{
+    /// Hop distances from `start`, see [`bfs`]. Like [`Graph::dijkstra`], unreached nodes get
+    /// `usize::MAX`.
+    pub fn bfs(
+        &self,
+        start: &T::NodeIdType,
+        end: Option<&T::NodeIdType>,
+    ) -> Dijkstra<T::NodeIdType> {
+        self.with_unreached(bfs(start.clone(), end, GraphSuccessors::new(self)))
+    }
+
+    /// [`Graph::dijkstra`] for graphs whose edges all cost 0 or 1, see [`zero_one_bfs`].
+    pub fn zero_one_bfs(
+        &self,
+        start: &T::NodeIdType,
+        end: Option<&T::NodeIdType>,
+    ) -> Dijkstra<T::NodeIdType> {
+        self.dijkstra_using(start, end, ZeroOneQueue::default())
+    }
+
+    /// Step-wise dijkstra from `start`, see [`DijkstraIter`].
+    pub fn dijkstra_iter(
+        &self,
+        start: &T::NodeIdType,
+    ) -> DijkstraIter<T::NodeIdType, GraphSuccessors<'_, T>> {
+        DijkstraIter::new(start.clone(), GraphSuccessors::new(self))
+    }
+
+    /// Step-wise breadth-first search from `start`, see [`BfsIter`].
+    pub fn bfs_iter(
+        &self,
+        start: &T::NodeIdType,
+    ) -> BfsIter<T::NodeIdType, GraphSuccessors<'_, T>> {
+        BfsIter::new(start.clone(), GraphSuccessors::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_graphs::*;
+
+    #[test]
+    fn test_dijkstra_iter_settle_order() {
+        let graph = create_test_graph(vec![(0, 1, 4), (1, 2, 3), (0, 2, 8), (0, 3, 1)]);
+
+        let settled: Vec<_> = graph.dijkstra_iter(&TestNodeId(0)).collect();
+        assert_eq!(
+            settled,
+            vec![
+                (TestNodeId(0), 0),
+                (TestNodeId(3), 1),
+                (TestNodeId(1), 4),
+                (TestNodeId(2), 7)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_iter_take_does_not_expand_last() {
+        let graph = create_test_graph(vec![(0, 1, 1), (1, 2, 1), (2, 3, 1)]);
+
+        let mut search = graph.dijkstra_iter(&TestNodeId(0));
+        let first_two: Vec<_> = search.by_ref().take(2).collect();
+        assert_eq!(first_two, vec![(TestNodeId(0), 0), (TestNodeId(1), 1)]);
+
+        // 1 was handed out but its edges have not been relaxed yet
+        assert_eq!(search.frontier().count(), 0);
+        assert!(!search.dist().contains_key(&TestNodeId(2)));
+
+        assert_eq!(search.next(), Some((TestNodeId(2), 2)));
+        let result = search.into_result();
+        assert_eq!(result.prev.get(&TestNodeId(2)), Some(&Some(TestNodeId(1))));
+        assert_eq!(result.dist.get(&TestNodeId(3)), None);
+    }
+
+    #[test]
+    fn test_dijkstra_iter_frontier() {
+        let graph = create_test_graph(vec![(0, 1, 5), (0, 2, 1), (2, 1, 1), (2, 3, 7)]);
+
+        let mut search = graph.dijkstra_iter(&TestNodeId(0));
+        search.next();
+        search.next(); // settles 2, 0 has been expanded
+
+        let mut frontier: Vec<_> = search.frontier().map(|(n, d)| (n.clone(), d)).collect();
+        frontier.sort();
+        assert_eq!(frontier, vec![(TestNodeId(1), 5)]);
+
+        search.next(); // expands 2, improving 1 and discovering 3
+        let mut frontier: Vec<_> = search.frontier().map(|(n, d)| (n.clone(), d)).collect();
+        frontier.sort();
+        assert_eq!(frontier, vec![(TestNodeId(3), 8)]);
+    }
+
+    #[test]
+    fn test_bfs_iter_ignores_costs() {
+        let graph = create_test_graph(vec![
+            (0, 1, 100),
+            (1, 2, 100),
+            (0, 3, 1),
+            (3, 4, 1),
+            (4, 2, 1),
+        ]);
+
+        let visited: HashMap<_, _> = graph.bfs_iter(&TestNodeId(0)).collect();
+        assert_eq!(visited[&TestNodeId(1)], 1);
+        assert_eq!(visited[&TestNodeId(3)], 1);
+        assert_eq!(visited[&TestNodeId(2)], 2);
+        assert_eq!(visited[&TestNodeId(4)], 2);
+
+        let order: Vec<_> = graph.bfs_iter(&TestNodeId(0)).map(|(_, d)| d).collect();
+        assert!(order.is_sorted());
+    }
+
+    #[test]
+    fn test_implicit_successors() {
+        #[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+        struct Pos(i32);
+        impl IdTrait for Pos {}
+
+        // walking right costs 1, jumping 5 ahead costs 3
+        let successors = |p: &Pos| [(Pos(p.0 + 1), 1), (Pos(p.0 + 5), 3)];
+        let (_, dist) = dijkstra_iter(Pos(0), successors)
+            .find(|(p, _)| *p == Pos(12))
+            .unwrap();
+        assert_eq!(dist, 8); // 0 -> 5 -> 10 -> 11 -> 12
+
+        let (_, hops) = bfs_iter(Pos(0), successors)
+            .find(|(p, _)| *p == Pos(12))
+            .unwrap();
+        assert_eq!(hops, 4);
+    }
+
+    #[test]
+    fn test_bfs_and_zero_one_bfs() {
+        // 5 is only reachable from itself
+        let graph = create_test_graph(vec![
+            (0, 1, 1),
+            (1, 2, 1),
+            (0, 3, 0),
+            (3, 4, 0),
+            (4, 2, 1),
+            (5, 0, 1),
+        ]);
+        let start = TestNodeId(0);
+
+        let hops = graph.bfs(&start, None);
+        assert_eq!(hops.dist[&TestNodeId(2)], 2);
+        assert_eq!(hops.dist[&TestNodeId(4)], 2);
+        assert_eq!(hops.dist[&TestNodeId(5)], usize::MAX);
+        assert_eq!(hops.prev[&TestNodeId(5)], None);
+
+        let zero_one = graph.zero_one_bfs(&start, None);
+        assert_eq!(zero_one.dist, graph.dijkstra(&start, None).dist);
+        assert_eq!(zero_one.dist[&TestNodeId(2)], 1);
+        assert_eq!(zero_one.prev[&TestNodeId(2)], Some(TestNodeId(4)));
+    }
+
+    #[test]
+    fn test_implicit_zero_one_bfs() {
+        // taking a lift up to the next of the 5 floors is free, walking along a floor costs 1
+        let successors = |&(floor, room): &(usize, usize)| {
+            let mut next = vec![((floor, room + 1), 1)];
+            if room % 3 == 0 && floor < 4 {
+                next.push(((floor + 1, room), 0));
+            }
+            next
+        };
+
+        let end = (4, 2);
+        let result = zero_one_bfs((0, 0), Some(&end), successors);
+        assert_eq!(result.dist[&end], 2);
+
+        let result = bfs((0, 0), Some(&end), successors);
+        assert_eq!(result.dist[&end], 6);
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::{Edge, Graph, IdTrait, Node};
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub(crate) struct TestNodeId(pub(crate) usize);
+
+impl Display for TestNodeId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}", self.0))
+    }
+}
+
+impl IdTrait for TestNodeId {}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TestNode {
+    pub(crate) id: TestNodeId,
+}
+
+impl Node for TestNode {
+    type NodeIdType = TestNodeId;
+
+    fn node_id(&self) -> Self::NodeIdType {
+        self.id.clone()
+    }
+}
+
+pub(crate) fn create_test_graph(edges: Vec<(usize, usize, usize)>) -> Graph<TestNode> {
+    let mut nodes = HashMap::new();
+    let mut adjacency_list: HashMap<TestNodeId, Vec<Edge<TestNode>>> = HashMap::new();
+
+    // Collect all unique node IDs
+    let mut node_ids: Vec<usize> = edges
+        .iter()
+        .flat_map(|(from, to, _)| vec![*from, *to])
+        .collect::<std::collections::HashSet<_>>()
+        .into_iter()
+        .collect();
+    node_ids.sort();
+
+    // Create nodes
+    for id in node_ids {
+        let node_id = TestNodeId(id);
+        nodes.insert(
+            node_id.clone(),
+            TestNode {
+                id: node_id.clone(),
+            },
+        );
+        adjacency_list.insert(node_id, Vec::new());
+    }
+
+    // Add edges
+    for (from, to, cost) in edges {
+        let from_id = TestNodeId(from);
+        let to_id = TestNodeId(to);
+        adjacency_list
+            .get_mut(&from_id)
+            .unwrap()
+            .push(Edge { node: to_id, cost });
+    }
+
+    Graph::new(nodes, adjacency_list)
+}
+
+pub(crate) fn ids(ids: &[usize]) -> Vec<TestNodeId> {
+    ids.iter().map(|&i| TestNodeId(i)).collect()
+}
@@ -0,0 +1,314 @@
+use std::fmt::Display;
+
+use crate::{Graph, Node};
+
+/// Largest number of points [`held_karp`] accepts, the table grows as `2^n * n`.
+pub const MAX_TSP_POINTS: usize = 20;
+
+/// What kind of route [`held_karp`] should look for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TourKind {
+    /// visit every point and return to where we started
+    Closed,
+    /// visit every point, starting and ending anywhere
+    Open,
+    /// visit every point, starting at the given one and ending anywhere
+    OpenFrom(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tour {
+    /// indices into the distance matrix, in visiting order. Closed tours start at point 0 and
+    /// don't repeat it at the end.
+    pub order: Vec<usize>,
+    pub cost: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TspError {
+    TooManyPoints {
+        points: usize,
+        max: usize,
+    },
+    NotSquare,
+    StartOutOfRange(usize),
+    /// some of the points can't be connected into a single route
+    NoRoute,
+}
+
+impl Display for TspError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TspError::TooManyPoints { points, max } => {
+                write!(
+                    f,
+                    "{points} points is too many, at most {max} are supported"
+                )
+            }
+            TspError::NotSquare => write!(f, "distance matrix is not square"),
+            TspError::StartOutOfRange(start) => write!(f, "start point {start} is out of range"),
+            TspError::NoRoute => write!(f, "no route visits every point"),
+        }
+    }
+}
+
+impl std::error::Error for TspError {}
+
+/// Finds the cheapest route visiting every point exactly once with the Held-Karp bitmask dynamic
+/// program.
+///
+/// `dist[a][b]` is the cost of going from point `a` to point `b`, `usize::MAX` meaning there is
+/// no way to get there. The matrix does not have to be symmetric. Routes whose cost doesn't fit
+/// in a `usize` count as impossible.
+pub fn held_karp(dist: &[Vec<usize>], kind: TourKind) -> Result<Tour, TspError> {
+    let n = dist.len();
+    if n > MAX_TSP_POINTS {
+        return Err(TspError::TooManyPoints {
+            points: n,
+            max: MAX_TSP_POINTS,
+        });
+    }
+    if dist.iter().any(|row| row.len() != n) {
+        return Err(TspError::NotSquare);
+    }
+
+    let fixed = match kind {
+        TourKind::Closed if n > 0 => Some(0),
+        TourKind::Closed | TourKind::Open => None,
+        TourKind::OpenFrom(start) if start < n => Some(start),
+        TourKind::OpenFrom(start) => return Err(TspError::StartOutOfRange(start)),
+    };
+
+    // the points that still have to be ordered
+    let others: Vec<_> = (0..n).filter(|&p| Some(p) != fixed).collect();
+    let k = others.len();
+    if k == 0 {
+        return Ok(Tour {
+            order: fixed.into_iter().collect(),
+            cost: 0,
+        });
+    }
+
+    // dp[mask * k + j]: cheapest path covering `mask` (plus the fixed start) that ends in others[j]
+    let full = (1 << k) - 1;
+    let mut dp = vec![usize::MAX; (1 << k) * k];
+    let mut parent = vec![u8::MAX; (1 << k) * k];
+
+    for (j, &p) in others.iter().enumerate() {
+        dp[(1 << j) * k + j] = match fixed {
+            Some(f) => dist[f][p],
+            None => 0,
+        };
+    }
+
+    for mask in 1..=full {
+        for j in (0..k).filter(|j| mask & (1 << j) != 0) {
+            let cost = dp[mask * k + j];
+            if cost == usize::MAX {
+                continue;
+            }
+
+            for t in (0..k).filter(|t| mask & (1 << t) == 0) {
+                let step = dist[others[j]][others[t]];
+                if step == usize::MAX {
+                    continue;
+                }
+
+                // a route too long to add up is as good as no route
+                let Some(alt) = cost.checked_add(step) else {
+                    continue;
+                };
+                let next = (mask | (1 << t)) * k + t;
+                if alt < dp[next] {
+                    dp[next] = alt;
+                    parent[next] = j as u8;
+                }
+            }
+        }
+    }
+
+    let closing_cost = |j: usize| match (kind, fixed) {
+        (TourKind::Closed, Some(f)) => dist[others[j]][f],
+        _ => 0,
+    };
+    let (cost, mut last) = (0..k)
+        .filter(|&j| dp[full * k + j] != usize::MAX && closing_cost(j) != usize::MAX)
+        .filter_map(|j| Some((dp[full * k + j].checked_add(closing_cost(j))?, j)))
+        .min()
+        .ok_or(TspError::NoRoute)?;
+
+    let mut order = vec![];
+    let mut mask = full;
+    loop {
+        order.push(others[last]);
+        let prev = parent[mask * k + last];
+        mask &= !(1 << last);
+        if prev == u8::MAX {
+            break;
+        }
+        last = prev as usize;
+    }
+    order.extend(fixed);
+    order.reverse();
+
+    Ok(Tour { order, cost })
+}
+
+impl<T> Graph<T>
+where
+    T: Node,
+{
+    /// Shortest-path distances between every pair of `points`, `usize::MAX` where there is no
+    /// path (or the point is not in the graph).
+    pub fn distance_matrix(&self, points: &[T::NodeIdType]) -> Vec<Vec<usize>> {
+        let indexed = self.indexed();
+        points
+            .iter()
+            .map(|from| match indexed.index.get(from) {
+                Some(&source) => {
+                    let dist = indexed.distances_from(source);
+                    points
+                        .iter()
+                        .map(|to| indexed.index.get(to).map_or(usize::MAX, |&t| dist[t]))
+                        .collect()
+                }
+                None => vec![usize::MAX; points.len()],
+            })
+            .collect()
+    }
+
+    /// Cheapest route through all `points`, where each leg follows a shortest path in the graph.
+    /// Returns the points in visiting order together with the total cost.
+    pub fn shortest_tour(
+        &self,
+        points: &[T::NodeIdType],
+        kind: TourKind,
+    ) -> Result<(Vec<T::NodeIdType>, usize), TspError> {
+        if points.len() > MAX_TSP_POINTS {
+            return Err(TspError::TooManyPoints {
+                points: points.len(),
+                max: MAX_TSP_POINTS,
+            });
+        }
+
+        let tour = held_karp(&self.distance_matrix(points), kind)?;
+        let order = tour.order.iter().map(|&i| points[i].clone()).collect();
+        Ok((order, tour.cost))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_graphs::*;
+
+    const X: usize = usize::MAX;
+
+    #[test]
+    fn test_closed_tour() {
+        let dist = vec![
+            vec![0, 10, 15, 20],
+            vec![10, 0, 35, 25],
+            vec![15, 35, 0, 30],
+            vec![20, 25, 30, 0],
+        ];
+        let tour = held_karp(&dist, TourKind::Closed).unwrap();
+
+        assert_eq!(tour.cost, 80);
+        assert!(tour.order == vec![0, 1, 3, 2] || tour.order == vec![0, 2, 3, 1]);
+    }
+
+    #[test]
+    fn test_open_tours() {
+        // points on a line at 0, 1, 5 and 6
+        let positions: [usize; 4] = [5, 0, 6, 1];
+        let dist: Vec<Vec<_>> = positions
+            .iter()
+            .map(|a| positions.iter().map(|b| a.abs_diff(*b)).collect())
+            .collect();
+
+        let tour = held_karp(&dist, TourKind::Open).unwrap();
+        assert_eq!(tour.cost, 6);
+        assert!(tour.order == vec![1, 3, 0, 2] || tour.order == vec![2, 0, 3, 1]);
+
+        // starting at 5 means doubling back one way or the other
+        let tour = held_karp(&dist, TourKind::OpenFrom(0)).unwrap();
+        assert_eq!(tour.cost, 7);
+        assert_eq!(tour.order, vec![0, 2, 3, 1]);
+    }
+
+    #[test]
+    fn test_asymmetric_and_unreachable() {
+        let dist = vec![vec![0, 1, X], vec![X, 0, 1], vec![1, X, 0]];
+        let tour = held_karp(&dist, TourKind::Closed).unwrap();
+        assert_eq!(
+            tour,
+            Tour {
+                order: vec![0, 1, 2],
+                cost: 3
+            }
+        );
+
+        let dist = vec![vec![0, 1, X], vec![X, 0, X], vec![X, X, 0]];
+        assert_eq!(held_karp(&dist, TourKind::Open), Err(TspError::NoRoute));
+
+        // each leg fits in a usize, but the round trip doesn't
+        let dist = vec![vec![0, X - 1], vec![X - 1, 0]];
+        assert_eq!(held_karp(&dist, TourKind::Closed), Err(TspError::NoRoute));
+        assert_eq!(held_karp(&dist, TourKind::Open).unwrap().cost, X - 1);
+    }
+
+    #[test]
+    fn test_degenerate_inputs() {
+        assert_eq!(
+            held_karp(&[], TourKind::Closed),
+            Ok(Tour {
+                order: vec![],
+                cost: 0
+            })
+        );
+        assert_eq!(
+            held_karp(&[vec![0]], TourKind::Closed),
+            Ok(Tour {
+                order: vec![0],
+                cost: 0
+            })
+        );
+        assert_eq!(
+            held_karp(&[vec![0, 1]], TourKind::Open),
+            Err(TspError::NotSquare)
+        );
+        assert_eq!(
+            held_karp(&[vec![0]], TourKind::OpenFrom(3)),
+            Err(TspError::StartOutOfRange(3))
+        );
+
+        let too_many = vec![vec![1; MAX_TSP_POINTS + 1]; MAX_TSP_POINTS + 1];
+        assert_eq!(
+            held_karp(&too_many, TourKind::Open),
+            Err(TspError::TooManyPoints {
+                points: 21,
+                max: 20
+            })
+        );
+    }
+
+    #[test]
+    fn test_shortest_tour_on_graph() {
+        // a corridor 0 - 1 - 2 - 3 - 4, visiting 4, 0 and 2
+        let graph = create_test_graph(
+            (0..4)
+                .flat_map(|i| [(i, i + 1, 1), (i + 1, i, 1)])
+                .collect(),
+        );
+        let points = ids(&[4, 0, 2]);
+
+        let (order, cost) = graph.shortest_tour(&points, TourKind::Closed).unwrap();
+        assert_eq!(cost, 8);
+        assert_eq!(order[0], TestNodeId(4));
+
+        let (order, cost) = graph.shortest_tour(&points, TourKind::Open).unwrap();
+        assert_eq!(cost, 4);
+        assert!(order == ids(&[4, 2, 0]) || order == ids(&[0, 2, 4]));
+    }
+}
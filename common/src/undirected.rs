@@ -0,0 +1,130 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::indexed::IndexedGraph;
+use crate::{Graph, IdTrait, Node};
+
+/// Undirected, simple view of a [`Graph`].
+///
+/// Every edge `a -> b` becomes the undirected edge `{a, b}`. Parallel edges (including an edge
+/// stored in both directions) collapse into one, and self-loops are dropped. Nodes are indexed in
+/// sorted order so that algorithms running over the view are deterministic.
+#[derive(Debug, Clone)]
+pub struct UndirectedView<N>
+where
+    N: IdTrait,
+{
+    ids: Vec<N>,
+    index: HashMap<N, usize>,
+    adj: Vec<Vec<usize>>,
+}
+
+impl<N> UndirectedView<N>
+where
+    N: IdTrait,
+{
+    /// All node ids, in ascending order.
+    pub fn nodes(&self) -> &[N] {
+        &self.ids
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.index.contains_key(node)
+    }
+
+    /// Neighbours of `node`, in ascending order.
+    pub fn neighbors(&self, node: &N) -> impl Iterator<Item = &N> + '_ {
+        self.index
+            .get(node)
+            .into_iter()
+            .flat_map(|&i| self.adj[i].iter().map(|&j| &self.ids[j]))
+    }
+
+    pub fn degree(&self, node: &N) -> usize {
+        self.index.get(node).map_or(0, |&i| self.adj[i].len())
+    }
+
+    pub fn has_edge(&self, a: &N, b: &N) -> bool {
+        match (self.index.get(a), self.index.get(b)) {
+            (Some(&i), Some(&j)) => self.adj[i].binary_search(&j).is_ok(),
+            _ => false,
+        }
+    }
+
+    /// Every edge once, as `(smaller, larger)` pairs in ascending order.
+    pub fn edges(&self) -> Vec<(N, N)> {
+        self.adj
+            .iter()
+            .enumerate()
+            .flat_map(|(i, adj)| {
+                adj.iter()
+                    .filter(move |&&j| i < j)
+                    .map(move |&j| (self.ids[i].clone(), self.ids[j].clone()))
+            })
+            .collect()
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.adj.iter().map(|adj| adj.len()).sum::<usize>() / 2
+    }
+
+    pub(crate) fn id(&self, i: usize) -> &N {
+        &self.ids[i]
+    }
+
+    pub(crate) fn adj(&self) -> &[Vec<usize>] {
+        &self.adj
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Node,
+{
+    /// Builds the undirected view of this graph, see [`UndirectedView`].
+    pub fn undirected(&self) -> UndirectedView<T::NodeIdType> {
+        let IndexedGraph {
+            ids,
+            index,
+            adj: directed,
+        } = self.indexed();
+
+        let mut adj = vec![BTreeSet::new(); ids.len()];
+        for (i, edges) in directed.iter().enumerate() {
+            for &(j, _) in edges {
+                if i != j {
+                    adj[i].insert(j);
+                    adj[j].insert(i);
+                }
+            }
+        }
+
+        UndirectedView {
+            ids,
+            index,
+            adj: adj.into_iter().map(|s| s.into_iter().collect()).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_graphs::*;
+
+    #[test]
+    fn test_undirected_view_collapses_edges() {
+        let graph = create_test_graph(vec![(0, 1, 1), (1, 0, 5), (1, 1, 2), (2, 1, 3)]);
+        let view = graph.undirected();
+
+        assert_eq!(view.nodes(), &[TestNodeId(0), TestNodeId(1), TestNodeId(2)]);
+        assert_eq!(
+            view.edges(),
+            vec![
+                (TestNodeId(0), TestNodeId(1)),
+                (TestNodeId(1), TestNodeId(2))
+            ]
+        );
+        assert_eq!(view.degree(&TestNodeId(1)), 2);
+        assert!(view.has_edge(&TestNodeId(2), &TestNodeId(1)));
+        assert!(!view.has_edge(&TestNodeId(0), &TestNodeId(2)));
+    }
+}
@@ -1,11 +1,6 @@
-use std::{
-    collections::HashSet,
-    hash::Hash,
-    iter::zip,
-    str::FromStr,
-};
+use std::{hash::Hash, str::FromStr};
 
-use common::read_lines;
+use common::{read_lines, Graph, PlainNode};
 
 #[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
 struct Node {
@@ -43,60 +38,27 @@ impl TopoMap {
         neighbors
     }
 
-    fn count_trails(&self, node: &Node, counts: &mut Vec<HashSet<Node>>) {
-        let idx = (node.col + node.row * self.width) as usize;
-        if node.height == 9 {
-            counts[idx].insert(*node);
-            println!("counts[{:?}]=1", idx);
-        } else {
-            // number of trails that pass through this node
-            counts[idx] = self
-                .get_neighbors(node)
-                .iter()
-                .filter(|n| n.height == node.height + 1)
-                .flat_map(|n| counts[(n.col + n.row * self.width) as usize].clone())
-                .collect();
-
-            println!("counts[{:?}]={:?}", idx, counts[idx].len());
-        }
+    fn position(node: &Node) -> (usize, usize) {
+        (node.col as usize, node.row as usize)
     }
-    fn count_all_trails(&self) -> Vec<HashSet<Node>> {
-        let mut counts = vec![];
-        counts.resize((self.height * self.width) as usize, HashSet::new());
-        for height in (0..10).rev() {
-            println!("counting for height: {}", height);
-            let height_nodes = self.nodes.iter().filter(|n| n.height == height);
-            for node in height_nodes {
-                self.count_trails(node, &mut counts);
-            }
-        }
-        counts
+
+    fn positions_at(&self, height: i32) -> Vec<(usize, usize)> {
+        self.nodes
+            .iter()
+            .filter(|n| n.height == height)
+            .map(Self::position)
+            .collect()
     }
 
-    fn count_trails_part2(&self, node: &Node, counts: &mut Vec<i32>) {
-        let idx = (node.col + node.row * self.width) as usize;
-        if node.height == 9 {
-            counts[idx] = 1;
-        } else {
-            // number of trails that pass through this node
-            counts[idx] = self
-                .get_neighbors(node)
-                .iter()
+    // every uphill step by exactly one is an edge
+    fn trail_graph(&self) -> Graph<PlainNode<(usize, usize)>> {
+        let edges = self.nodes.iter().flat_map(|node| {
+            self.get_neighbors(node)
+                .into_iter()
                 .filter(|n| n.height == node.height + 1)
-                .map(|n| counts[(n.col + n.row * self.width) as usize])
-                .sum();
-        }
-    }
-    fn count_all_trails_part2(&self) -> Vec<i32> {
-        let mut counts = vec![];
-        counts.resize((self.height * self.width) as usize, 0);
-        for height in (0..10).rev() {
-            let height_nodes = self.nodes.iter().filter(|n| n.height == height);
-            for node in height_nodes {
-                self.count_trails_part2(node, &mut counts);
-            }
-        }
-        counts
+                .map(|n| (Self::position(node), Self::position(n), 1))
+        });
+        Graph::from_edges(self.nodes.iter().map(Self::position), edges)
     }
 }
 
@@ -137,12 +99,14 @@ fn part1() {
         .collect::<Vec<_>>()
         .join("\n");
     let topo_map: TopoMap = grid_lines.parse().unwrap();
-    let counts = topo_map.count_all_trails();
-    println!("{:?}", counts);
-    // for (node, count) in
-    let num_trails: i32 = zip(topo_map.nodes, counts)
-        .filter(|(n, _)| n.height == 0)
-        .map(|(_, count)| count.len() as i32)
+    let closure = topo_map.trail_graph().transitive_closure().unwrap();
+    let peaks = topo_map.positions_at(9);
+
+    // the score of a trailhead is the number of peaks it can reach
+    let num_trails: usize = topo_map
+        .positions_at(0)
+        .iter()
+        .map(|head| peaks.iter().filter(|p| closure.reaches(head, p)).count())
         .sum();
     println!("{}", num_trails);
 }
@@ -153,12 +117,10 @@ fn part2() {
         .collect::<Vec<_>>()
         .join("\n");
     let topo_map: TopoMap = grid_lines.parse().unwrap();
-    let counts = topo_map.count_all_trails_part2();
-
-    let num_trails: i32 = zip(topo_map.nodes, counts)
-        .filter(|(n, _)| n.height == 0)
-        .map(|(_, count)| count)
-        .sum();
+    let num_trails = topo_map
+        .trail_graph()
+        .count_paths(&topo_map.positions_at(0), &topo_map.positions_at(9))
+        .unwrap();
     println!("{}", num_trails);
 }
 fn main() {